@@ -18,6 +18,14 @@ pub fn hexagon_polygon_access_benchmark(c: &mut Criterion) {
     });
 }
 
+pub fn hexagon_vertex_access_benchmark(c: &mut Criterion) {
+    let hex = Hexagon::new(Point::new(200.0, 100.0), 100.0, Angle::zero());
+
+    c.bench_function("hexagon vertex access", |b| {
+        b.iter(|| access_hexagon_vertices(black_box(&hex)))
+    });
+}
+
 pub fn modify_hexagon(hex: &mut Hexagon) {
     let rot = Angle::new(1.0);
     for _i in 0..100 {
@@ -32,8 +40,15 @@ pub fn access_hexagon_polygon(hex: &Hexagon) {
     }
 }
 
+pub fn access_hexagon_vertices(hex: &Hexagon) {
+    for _i in 0..100 {
+        let _point = hex.vertices().first();
+    }
+}
+
 criterion_group!(
     polygons,
     hexagon_modification_benchmark,
-    hexagon_polygon_access_benchmark
+    hexagon_polygon_access_benchmark,
+    hexagon_vertex_access_benchmark
 );
@@ -24,262 +24,121 @@ pub const MOVING_POS: Point = Point {
     y: ORIGIN.1 - 150.0,
 };
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum ObjectShape {
-    Point,
-    BoundingBox,
-    Rectangle,
-    Circle,
-    Ray,
-}
+// the kinds Q and W cycle through
+pub const KINDS: [ShapeType; 6] = [
+    ShapeType::Point,
+    ShapeType::Circle,
+    ShapeType::BoundingBox,
+    ShapeType::Rectangle,
+    ShapeType::Hexagon,
+    ShapeType::Ray,
+];
+pub const ROTATING: usize = 2;
+pub const MOVING: usize = 3;
 
-pub struct Object {
-    circle: Circle,
-    point: Point,
-    bounding_box: BoundingBox,
-    rectangle: Rectangle,
-    ray: Ray,
+pub fn next_kind(kind: ShapeType) -> ShapeType {
+    let index = KINDS.iter().position(|&other| other == kind).unwrap();
+    KINDS[(index + 1) % KINDS.len()]
 }
-impl Object {
-    fn default() -> Self {
-        Self {
-            circle: Circle::new(Point::zero(), 0.0),
-            point: Point::zero(),
-            bounding_box: BoundingBox::new(Point::zero(), Size::zero()),
-            rectangle: Rectangle::new(Point::zero(), Size::zero(), Angle::zero()),
-            ray: Ray::new(Point::zero(), Angle::zero(), 0.0),
-        }
-    }
-    fn rotate(&mut self, angle: Angle) {
-        self.ray.direction = self.ray.direction + angle;
-        self.rectangle.rotate(angle);
-    }
-    fn translate(&mut self, translation: Vector) {
-        self.point = self.point + translation;
-        self.circle.move_to(self.point);
-        self.bounding_box.center = self.point;
-        self.rectangle.move_to(self.point);
-        self.ray.origin = self.point;
-    }
-    fn move_to(&mut self, location: Point) {
-        self.point = location;
-        self.circle.move_to(location);
-        self.bounding_box.center = location;
-        self.rectangle.move_to(location);
-        self.ray.origin = location;
-    }
-    fn hit(
-        &self,
-        self_shape: ObjectShape,
-        other: &Object,
-        other_shape: ObjectShape,
-    ) -> Option<Hit> {
-        match self_shape {
-            ObjectShape::BoundingBox => match other_shape {
-                ObjectShape::Point => {
-                    return self.bounding_box.hit_point(other.point);
-                }
-                ObjectShape::Circle => {
-                    return self.bounding_box.hit_circle(&other.circle);
-                }
-                ObjectShape::BoundingBox => {
-                    return self.bounding_box.hit_bounding_box(other.bounding_box);
-                }
-                ObjectShape::Rectangle => {
-                    return None; //self.bounding_box.hit_rectangle(other.bounding_box);
-                }
-                ObjectShape::Ray => {
-                    return None;
-                }
-            },
-            ObjectShape::Rectangle => match other_shape {
-                ObjectShape::Point => {
-                    return self.rectangle.hit_point(other.point);
-                }
-                ObjectShape::Circle => {
-                    return self.rectangle.hit_circle(&other.circle);
-                }
-                ObjectShape::BoundingBox => {
-                    return self.rectangle.hit_bounding_box(other.bounding_box);
-                }
-                ObjectShape::Rectangle => {
-                    return None; //self.rectangle.hit_rectangle(other.bounding_box);
-                }
-                ObjectShape::Ray => {
-                    return None;
-                }
-            },
-            ObjectShape::Ray => match other_shape {
-                ObjectShape::Point => {
-                    return self.ray.hit_point(other.point);
-                }
-                ObjectShape::Circle => {
-                    return self.ray.hit_circle(&other.circle);
-                }
-                ObjectShape::BoundingBox => {
-                    return self.ray.hit_bounding_box(other.bounding_box);
-                }
-                ObjectShape::Rectangle => {
-                    return None; //self.ray.hit_rectangle(other.bounding_box);
-                }
-                ObjectShape::Ray => {
-                    return None;
-                }
-            },
-            ObjectShape::Circle => match other_shape {
-                ObjectShape::Point => {
-                    return self.circle.hit_point(other.point);
-                }
-                ObjectShape::Circle => {
-                    return self.circle.hit_circle(&other.circle);
-                }
-                ObjectShape::BoundingBox => {
-                    return self.circle.hit_bounding_box(other.bounding_box);
-                }
-                ObjectShape::Rectangle => {
-                    return None; //self.circle.hit_rectangle(other.bounding_box);
-                }
-                ObjectShape::Ray => {
-                    return None;
-                }
-            },
-            _ => None,
-        }
+
+// sized by half of its extent, circles, hexagons and rays by the width alone
+pub fn spawn(kind: ShapeType, position: Point, half: Size, direction: Angle) -> AnyShape {
+    match kind {
+        ShapeType::Point => position.into(),
+        ShapeType::Circle => Circle::new(position, half.w).into(),
+        ShapeType::BoundingBox => BoundingBox::new(position, half).into(),
+        ShapeType::Rectangle => Rectangle::new(position, half * 2.0, direction).into(),
+        ShapeType::Hexagon => Hexagon::new(position, half.w, direction).into(),
+        ShapeType::Ray => Ray::new(position, direction, half.w).into(),
+        _ => panic!("Expected one of the playground kinds"),
     }
 }
 
 struct GameState {
-    controlled_object: Object,
-    controlled_shape: ObjectShape,
-    object_shape: ObjectShape,
-    small_object: Object,
-    large_object: Object,
-    rotating_object: Object,
-    moving_object: Object,
-    hit_small: Option<Hit>,
-    hit_large: Option<Hit>,
-    hit_rotating: Option<Hit>,
-    hit_moving: Option<Hit>,
+    controlled_kind: ShapeType,
+    object_kind: ShapeType,
+    controlled: AnyShape,
+    // small, large, rotating and moving
+    objects: Vec<AnyShape>,
+    hit: Option<Hit>,
 }
 
 impl GameState {
     pub fn new() -> Self {
         Self {
-            controlled_object: Object::default(),
-            controlled_shape: ObjectShape::BoundingBox,
-            object_shape: ObjectShape::BoundingBox,
-            small_object: Object::default(),
-            large_object: Object::default(),
-            rotating_object: Object::default(),
-            moving_object: Object::default(),
-            hit_small: None,
-            hit_large: None,
-            hit_rotating: None,
-            hit_moving: None,
+            controlled_kind: ShapeType::BoundingBox,
+            object_kind: ShapeType::BoundingBox,
+            controlled: Point::zero().into(),
+            objects: Vec::new(),
+            hit: None,
         }
     }
     pub fn respawn(&mut self) {
-        self.controlled_object.point = Point::zero();
-        self.small_object.point = SMALL_POS.clone();
-        self.large_object.point = LARGE_POS.clone();
-        self.rotating_object.point = ROTATING_POS.clone();
-        self.moving_object.point = MOVING_POS.clone();
-
-        self.controlled_object.circle = Circle::new(Point::zero(), 50.0);
-        self.small_object.circle = Circle::new(SMALL_POS, 20.0);
-        self.large_object.circle = Circle::new(LARGE_POS, 100.0);
-        self.rotating_object.circle = Circle::new(ROTATING_POS, 50.0);
-        self.moving_object.circle = Circle::new(MOVING_POS, 50.0);
-
-        self.controlled_object.bounding_box =
-            BoundingBox::new(Point::zero(), Size::new(50.0, 20.0));
-        self.small_object.bounding_box = BoundingBox::new(SMALL_POS, Size::new(20.0, 10.0));
-        self.large_object.bounding_box = BoundingBox::new(LARGE_POS, Size::new(100.0, 30.0));
-        self.rotating_object.bounding_box = BoundingBox::new(ROTATING_POS, Size::new(50.0, 50.0));
-        self.moving_object.bounding_box = BoundingBox::new(MOVING_POS, Size::new(50.0, 50.0));
-
-        self.controlled_object.rectangle =
-            Rectangle::new(Point::zero(), Size::new(50.0, 20.0) * 2.0, Angle::zero());
-        self.small_object.rectangle =
-            Rectangle::new(SMALL_POS, Size::new(20.0, 10.0) * 2.0, Angle::zero());
-        self.large_object.rectangle =
-            Rectangle::new(LARGE_POS, Size::new(100.0, 30.0) * 2.0, Angle::zero());
-        self.rotating_object.rectangle =
-            Rectangle::new(ROTATING_POS, Size::new(50.0, 50.0) * 2.0, Angle::zero());
-        self.moving_object.rectangle =
-            Rectangle::new(MOVING_POS, Size::new(50.0, 50.0) * 2.0, Angle::zero());
-
-        self.controlled_object.ray = Ray::new(Point::zero(), Angle::zero(), 50.0);
-        self.small_object.ray = Ray::new(SMALL_POS, Angle::zero(), 20.0);
-        self.large_object.ray = Ray::new(LARGE_POS, Angle::new(180.0), 100.0);
-        self.rotating_object.ray = Ray::new(ROTATING_POS, Angle::zero(), 50.0);
-        self.moving_object.ray = Ray::new(MOVING_POS, Angle::zero(), 50.0);
+        self.controlled = spawn(
+            self.controlled_kind,
+            Point::zero(),
+            Size::new(50.0, 20.0),
+            Angle::zero(),
+        );
+        self.objects = vec![
+            spawn(
+                self.object_kind,
+                SMALL_POS,
+                Size::new(20.0, 10.0),
+                Angle::zero(),
+            ),
+            spawn(
+                self.object_kind,
+                LARGE_POS,
+                Size::new(100.0, 30.0),
+                Angle::new(180.0),
+            ),
+            spawn(
+                self.object_kind,
+                ROTATING_POS,
+                Size::new(50.0, 50.0),
+                Angle::zero(),
+            ),
+            spawn(
+                self.object_kind,
+                MOVING_POS,
+                Size::new(50.0, 50.0),
+                Angle::zero(),
+            ),
+        ];
     }
 }
 
 impl event::EventHandler for GameState {
     fn update(&mut self, ctx: &mut Context) -> GameResult {
         let mouse = input::mouse::position(ctx);
-        self.controlled_object.move_to(Point::new(mouse.x, mouse.y));
+        self.controlled.move_to(Point::new(mouse.x, mouse.y));
 
-        self.rotating_object.rotate(Angle::new(1.0));
-        self.moving_object.translate(Vector::new(1.0, 0.0));
-        if self.moving_object.point.x > SCREEN_SIZE.0 {
-            self.moving_object
-                .translate(Vector::new(-SCREEN_SIZE.0 / 2.0, 0.0));
+        self.objects[ROTATING].rotate(Angle::new(1.0));
+        self.objects[MOVING].translate(Vector::new(1.0, 0.0));
+        if self.objects[MOVING].center().x > SCREEN_SIZE.0 {
+            self.objects[MOVING].translate(Vector::new(-SCREEN_SIZE.0 / 2.0, 0.0));
         }
 
-        self.hit_small = self.controlled_object.hit(
-            self.controlled_shape,
-            &self.small_object,
-            self.object_shape,
-        );
-        self.hit_large = self.controlled_object.hit(
-            self.controlled_shape,
-            &self.large_object,
-            self.object_shape,
-        );
-        self.hit_rotating = self.controlled_object.hit(
-            self.controlled_shape,
-            &self.rotating_object,
-            self.object_shape,
-        );
-        self.hit_moving = self.controlled_object.hit(
-            self.controlled_shape,
-            &self.moving_object,
-            self.object_shape,
-        );
+        let controlled = &self.controlled;
+        self.hit = self
+            .objects
+            .iter()
+            .find_map(|object| collide(controlled, object));
 
         Ok(())
     }
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
         graphics::clear(ctx, [0.0, 0.0, 0.0, 1.0].into());
 
-        draw_object(ctx, &self.small_object, self.object_shape, graphics::WHITE)?;
-        draw_object(ctx, &self.large_object, self.object_shape, graphics::WHITE)?;
-        draw_object(
-            ctx,
-            &self.rotating_object,
-            self.object_shape,
-            graphics::WHITE,
-        )?;
-        draw_object(ctx, &self.moving_object, self.object_shape, graphics::WHITE)?;
+        for object in self.objects.iter() {
+            draw_shape(ctx, object, graphics::WHITE)?;
+        }
 
-        if let Some(hit) = self.hit_small {
-            draw_hit_object(ctx, &self.controlled_object, self.controlled_shape, hit)?;
-        } else if let Some(hit) = self.hit_large {
-            draw_hit_object(ctx, &self.controlled_object, self.controlled_shape, hit)?;
-        } else if let Some(hit) = self.hit_rotating {
-            draw_hit_object(ctx, &self.controlled_object, self.controlled_shape, hit)?;
-        } else if let Some(hit) = self.hit_moving {
-            draw_hit_object(ctx, &self.controlled_object, self.controlled_shape, hit)?;
+        if let Some(hit) = self.hit {
+            draw_hit_shape(ctx, &self.controlled, hit)?;
         } else {
-            draw_object(
-                ctx,
-                &self.controlled_object,
-                self.controlled_shape,
-                [0.0, 0.0, 1.0, 1.0].into(),
-            )?;
+            draw_shape(ctx, &self.controlled, [0.0, 0.0, 1.0, 1.0].into())?;
         }
         graphics::present(ctx)?;
         Ok(())
@@ -287,43 +146,11 @@ impl event::EventHandler for GameState {
     fn key_up_event(&mut self, _ctx: &mut Context, keycode: KeyCode, _keymod: KeyMods) {
         match keycode {
             KeyCode::Q => {
-                match self.object_shape {
-                    ObjectShape::Point => {
-                        self.object_shape = ObjectShape::Circle;
-                    }
-                    ObjectShape::Circle => {
-                        self.object_shape = ObjectShape::BoundingBox;
-                    }
-                    ObjectShape::BoundingBox => {
-                        self.object_shape = ObjectShape::Rectangle;
-                    }
-                    ObjectShape::Rectangle => {
-                        self.object_shape = ObjectShape::Ray;
-                    }
-                    ObjectShape::Ray => {
-                        self.object_shape = ObjectShape::Point;
-                    }
-                }
+                self.object_kind = next_kind(self.object_kind);
                 self.respawn();
             }
             KeyCode::W => {
-                match self.controlled_shape {
-                    ObjectShape::Point => {
-                        self.controlled_shape = ObjectShape::Circle;
-                    }
-                    ObjectShape::Circle => {
-                        self.controlled_shape = ObjectShape::BoundingBox;
-                    }
-                    ObjectShape::BoundingBox => {
-                        self.controlled_shape = ObjectShape::Rectangle;
-                    }
-                    ObjectShape::Rectangle => {
-                        self.controlled_shape = ObjectShape::Ray;
-                    }
-                    ObjectShape::Ray => {
-                        self.controlled_shape = ObjectShape::Point;
-                    }
-                }
+                self.controlled_kind = next_kind(self.controlled_kind);
                 self.respawn();
             }
             _ => (),
@@ -336,7 +163,7 @@ impl event::EventHandler for GameState {
         _x: f32,
         _y: f32,
     ) {
-        self.controlled_object.rotate(Angle::new(30f64));
+        self.controlled.rotate(Angle::new(30f64));
     }
 }
 
@@ -352,17 +179,36 @@ fn main() -> GameResult {
     event::run(ctx, events_loop, state)
 }
 
-pub fn draw_rectangle(
-    ctx: &mut Context,
-    rectangle: &Rectangle,
-    color: graphics::Color,
-) -> GameResult {
-    let mut vertices: [mint::Point2<f32>; 4] = [mint::Point2 { x: 0.0, y: 0.0 }; 4];
-    for (idx, vertex) in rectangle.polygon().vertices.iter().enumerate() {
-        vertices[idx].x = vertex.x;
-        vertices[idx].y = vertex.y;
+pub fn draw_outline(ctx: &mut Context, vertices: &[Point], color: graphics::Color) -> GameResult {
+    let fill = graphics::Color::new(color.r, color.g, color.b, color.a * 0.25);
+    fill_polygon(ctx, &Polygon::new(vertices), fill)?;
+    let points: Vec<mint::Point2<f32>> = vertices
+        .iter()
+        .map(|vertex| mint::Point2 {
+            x: vertex.x,
+            y: vertex.y,
+        })
+        .collect();
+    let mesh = graphics::Mesh::new_polygon(ctx, graphics::DrawMode::stroke(3.0), &points, color)?;
+    graphics::draw(ctx, &mesh, (ggez::mint::Point2 { x: 0.0, y: 0.0 },))?;
+    Ok(())
+}
+pub fn fill_polygon(ctx: &mut Context, polygon: &Polygon, color: graphics::Color) -> GameResult {
+    let mut vertices = Vec::new();
+    for triangle in polygon.triangles() {
+        for vertex in [triangle.a, triangle.b, triangle.c].iter() {
+            vertices.push(mint::Point2 {
+                x: vertex.x,
+                y: vertex.y,
+            });
+        }
+    }
+    if vertices.is_empty() {
+        return Ok(());
     }
-    let mesh = graphics::Mesh::new_polygon(ctx, graphics::DrawMode::stroke(3.0), &vertices, color)?;
+    let mesh = graphics::MeshBuilder::new()
+        .triangles(&vertices, color)?
+        .build(ctx)?;
     graphics::draw(ctx, &mesh, (ggez::mint::Point2 { x: 0.0, y: 0.0 },))?;
     Ok(())
 }
@@ -467,69 +313,28 @@ pub fn draw_hit(ctx: &mut Context, hit: Hit) -> GameResult {
     draw_point(ctx, hit.contact, [0.0, 1.0, 1.0, 1.0].into())?;
     Ok(())
 }
-pub fn draw_hit_object(
-    ctx: &mut Context,
-    object: &Object,
-    shape: ObjectShape,
-    hit: Hit,
-) -> GameResult {
-    match shape {
-        ObjectShape::Point => {
-            draw_point(ctx, object.point, [1.0, 0.0, 0.0, 1.0].into())?;
-            draw_point(ctx, object.point + hit.delta, [0.0, 1.0, 0.0, 1.0].into())?;
-        }
-        ObjectShape::Circle => {
-            draw_circle(ctx, &object.circle, [1.0, 0.0, 0.0, 1.0].into())?;
-            let mut adjusted = object.circle.clone();
-            adjusted.translate(hit.delta);
-            draw_circle(ctx, &adjusted, [0.0, 1.0, 0.0, 1.0].into())?;
-        }
-        ObjectShape::BoundingBox => {
-            draw_bounding_box(ctx, object.bounding_box, [1.0, 0.0, 0.0, 1.0].into())?;
-            let mut adjusted = object.bounding_box.clone();
-            adjusted.center = adjusted.center + hit.delta;
-            draw_bounding_box(ctx, adjusted, [0.0, 1.0, 0.0, 1.0].into())?;
-        }
-        ObjectShape::Rectangle => {
-            draw_rectangle(ctx, &object.rectangle, [1.0, 0.0, 0.0, 1.0].into())?;
-            let mut adjusted = object.rectangle.clone();
-            adjusted.translate(hit.delta);
-            draw_rectangle(ctx, &adjusted, [0.0, 1.0, 0.0, 1.0].into())?;
-        }
-        ObjectShape::Ray => {
-            draw_ray(ctx, object.ray, [1.0, 0.0, 0.0, 1.0].into())?;
-            if hit.time > 0.0 {
-                let mut hit_ray = object.ray.clone();
-                hit_ray.length = hit_ray.length * hit.time;
-                draw_ray(ctx, hit_ray, [0.0, 1.0, 0.0, 1.0].into())?;
-            }
+pub fn draw_hit_shape(ctx: &mut Context, shape: &AnyShape, hit: Hit) -> GameResult {
+    draw_shape(ctx, shape, [1.0, 0.0, 0.0, 1.0].into())?;
+    if let ShapeValue::Ray(ray) = shape.value() {
+        if hit.time > 0.0 {
+            let mut hit_ray = *ray;
+            hit_ray.length *= hit.time;
+            draw_ray(ctx, hit_ray, [0.0, 1.0, 0.0, 1.0].into())?;
         }
+    } else {
+        let mut adjusted = shape.clone();
+        adjusted.translate(hit.delta);
+        draw_shape(ctx, &adjusted, [0.0, 1.0, 0.0, 1.0].into())?;
     }
     draw_hit(ctx, hit)?;
     Ok(())
 }
-pub fn draw_object(
-    ctx: &mut Context,
-    object: &Object,
-    shape: ObjectShape,
-    color: graphics::Color,
-) -> GameResult {
-    match shape {
-        ObjectShape::Point => {
-            draw_point(ctx, object.point, color)?;
-        }
-        ObjectShape::Circle => {
-            draw_circle(ctx, &object.circle, color)?;
-        }
-        ObjectShape::BoundingBox => {
-            draw_bounding_box(ctx, object.bounding_box, color)?;
-        }
-        ObjectShape::Rectangle => {
-            draw_rectangle(ctx, &object.rectangle, color)?;
-        }
-        ObjectShape::Ray => {
-            draw_ray(ctx, object.ray, color)?;
-        }
+pub fn draw_shape(ctx: &mut Context, shape: &AnyShape, color: graphics::Color) -> GameResult {
+    match shape.value() {
+        ShapeValue::Point(point) => draw_point(ctx, *point, color),
+        ShapeValue::BoundingBox(bounding_box) => draw_bounding_box(ctx, *bounding_box, color),
+        ShapeValue::Ray(ray) => draw_ray(ctx, *ray, color),
+        ShapeValue::Circle(circle) => draw_circle(ctx, circle, color),
+        _ => draw_outline(ctx, shape.vertices(), color),
     }
-    Ok(())
 }
@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::algorithm::delaunay::Triangulation;
+use crate::base::Point;
+use crate::shape::{MultiPolygon, PointLocation, Polygon, PolygonWithHoles};
+
+// times a ring edge is halved before the overlay gives up on it
+const MAX_SPLITS: usize = 8;
+
+// Which winding numbers count as filled when resolving overlapping rings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero,
+    Positive,
+    Negative,
+}
+
+impl FillRule {
+    pub fn is_filled(&self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+            FillRule::Positive => winding > 0,
+            FillRule::Negative => winding < 0,
+        }
+    }
+}
+
+// All results have counter-clockwise outer rings, clockwise holes and no collinear vertices.
+// None if some ring edge could not be made part of the triangulation, the triangles
+// on either side of it cannot be told apart then.
+pub fn union<A: Into<MultiPolygon>, B: Into<MultiPolygon>>(a: A, b: B) -> Option<MultiPolygon> {
+    overlay(&a.into(), &b.into(), |in_a, in_b| in_a || in_b)
+}
+
+pub fn intersection<A: Into<MultiPolygon>, B: Into<MultiPolygon>>(
+    a: A,
+    b: B,
+) -> Option<MultiPolygon> {
+    overlay(&a.into(), &b.into(), |in_a, in_b| in_a && in_b)
+}
+
+pub fn difference<A: Into<MultiPolygon>, B: Into<MultiPolygon>>(
+    a: A,
+    b: B,
+) -> Option<MultiPolygon> {
+    overlay(&a.into(), &b.into(), |in_a, in_b| in_a && !in_b)
+}
+
+pub fn xor<A: Into<MultiPolygon>, B: Into<MultiPolygon>>(a: A, b: B) -> Option<MultiPolygon> {
+    overlay(&a.into(), &b.into(), |in_a, in_b| in_a != in_b)
+}
+
+// Merges arbitrary, possibly self-intersecting rings into clean polygons. With
+// `FillRule::NonZero` this is the union of any number of counter-clockwise outlines.
+pub fn resolve(rings: &[Polygon], rule: FillRule) -> Option<MultiPolygon> {
+    let triangulation = triangulate_rings(rings.iter().map(|ring| &ring.vertices[..]))?;
+    let selected = triangulation
+        .triangle_ids()
+        .filter(|&id| {
+            let centroid = triangulation.triangle(id).centroid();
+            let winding: i32 = rings
+                .iter()
+                .map(|ring| winding_number(&ring.vertices, centroid))
+                .sum();
+            rule.is_filled(winding)
+        })
+        .collect();
+    Some(regions(&triangulation, &selected))
+}
+
+pub fn winding_number(vertices: &[Point], point: Point) -> i32 {
+    let mut winding = 0;
+    for i in 0..vertices.len() {
+        let a = vertices[i];
+        let b = vertices[(i + 1) % vertices.len()];
+        let side = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+        if a.y <= point.y {
+            if b.y > point.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+fn overlay(
+    a: &MultiPolygon,
+    b: &MultiPolygon,
+    keep: impl Fn(bool, bool) -> bool,
+) -> Option<MultiPolygon> {
+    let rings = a
+        .polygons
+        .iter()
+        .chain(b.polygons.iter())
+        .flat_map(|polygon| polygon.rings())
+        .map(|ring| &ring.vertices[..]);
+    let triangulation = triangulate_rings(rings)?;
+    // every triangle lies entirely inside or outside each operand, so its centroid decides
+    let selected = triangulation
+        .triangle_ids()
+        .filter(|&id| {
+            let centroid = triangulation.triangle(id).centroid();
+            keep(
+                a.locate(centroid) != PointLocation::Outside,
+                b.locate(centroid) != PointLocation::Outside,
+            )
+        })
+        .collect();
+    Some(regions(&triangulation, &selected))
+}
+
+// All vertices go in before any edge, so the edges are forced into a triangulation
+// that already has faces. None if an edge still fails after splitting it.
+fn triangulate_rings<'a>(rings: impl Iterator<Item = &'a [Point]>) -> Option<Triangulation> {
+    let mut triangulation = Triangulation::new();
+    let rings: Vec<Vec<usize>> = rings
+        .map(|ring| ring.iter().map(|&p| triangulation.insert(p)).collect())
+        .collect();
+    for ring in rings.iter() {
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            if !constrain(&mut triangulation, a, b, MAX_SPLITS) {
+                return None;
+            }
+        }
+    }
+    Some(triangulation)
+}
+
+// Near coincident edges and touching vertices can leave the walk along an edge without
+// a face to start from. Halving the edge at a new vertex usually gets around that.
+fn constrain(triangulation: &mut Triangulation, a: usize, b: usize, splits: usize) -> bool {
+    if triangulation.add_constraint(a, b) {
+        return true;
+    }
+    if splits == 0 {
+        return false;
+    }
+    let (pa, pb) = (triangulation.points()[a], triangulation.points()[b]);
+    let middle = triangulation.insert(Point::new((pa.x + pb.x) / 2.0, (pa.y + pb.y) / 2.0));
+    if middle == a || middle == b {
+        return false;
+    }
+    constrain(triangulation, a, middle, splits - 1)
+        && constrain(triangulation, middle, b, splits - 1)
+}
+
+// traces the outline of the selected triangles into polygons with holes
+fn regions(triangulation: &Triangulation, selected: &HashSet<usize>) -> MultiPolygon {
+    let points = triangulation.points();
+    let mut edges = Vec::new();
+    for &id in selected.iter() {
+        let vertices = triangulation.vertices(id);
+        for (k, neighbor) in triangulation.neighbors(id).iter().enumerate() {
+            let outside = match neighbor {
+                Some(neighbor) => !selected.contains(neighbor),
+                None => true,
+            };
+            if outside {
+                edges.push((vertices[(k + 1) % 3], vertices[(k + 2) % 3]));
+            }
+        }
+    }
+    edges.sort_unstable();
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (idx, &(from, _)) in edges.iter().enumerate() {
+        outgoing.entry(from).or_default().push(idx);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut outers = Vec::new();
+    let mut holes = Vec::new();
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut current = start;
+        loop {
+            used[current] = true;
+            let (from, to) = edges[current];
+            ring.push(points[from]);
+            if to == edges[start].0 {
+                break;
+            }
+            // at vertices where the outline touches itself take the leftmost turn,
+            // which splits it into separate simple rings
+            let next = outgoing[&to]
+                .iter()
+                .copied()
+                .filter(|&idx| !used[idx])
+                .max_by(|&x, &y| {
+                    let turn =
+                        |idx: usize| turn_angle(points[from], points[to], points[edges[idx].1]);
+                    turn(x).partial_cmp(&turn(y)).unwrap()
+                });
+            current = match next {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        let ring = Polygon {
+            vertices: remove_collinear(ring),
+        };
+        if ring.vertices.len() < 3 {
+            continue;
+        }
+        let area = ring.signed_area();
+        if area > 0.0 {
+            outers.push(ring);
+        } else if area < 0.0 {
+            holes.push(ring);
+        }
+    }
+    let mut polygons: Vec<PolygonWithHoles> =
+        outers.into_iter().map(|outer| outer.into()).collect();
+    for hole in holes {
+        // edges are never shared between rings, so an edge midpoint is strictly inside or outside
+        let probe = (hole.vertices[0] + hole.vertices[1]) / 2.0;
+        let owner = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon)| polygon.outer.locate(probe) == PointLocation::Inside)
+            .min_by(|(_, x), (_, y)| x.outer.area().partial_cmp(&y.outer.area()).unwrap())
+            .map(|(idx, _)| idx);
+        if let Some(idx) = owner {
+            polygons[idx].holes.push(hole);
+        }
+    }
+    MultiPolygon::new(polygons)
+}
+
+// counter-clockwise angle from the direction a -> b to b -> c, in (-pi, pi]
+fn turn_angle(a: Point, b: Point, c: Point) -> f64 {
+    let (ux, uy) = (b.x as f64 - a.x as f64, b.y as f64 - a.y as f64);
+    let (vx, vy) = (c.x as f64 - b.x as f64, c.y as f64 - b.y as f64);
+    (ux * vy - uy * vx).atan2(ux * vx + uy * vy)
+}
+
+fn remove_collinear(mut ring: Vec<Point>) -> Vec<Point> {
+    let mut i = 0;
+    let mut unchanged = 0;
+    while ring.len() >= 3 && unchanged < ring.len() {
+        let n = ring.len();
+        i %= n;
+        let angle = turn_angle(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        if angle.abs() < 1e-6 {
+            ring.remove(i);
+            unchanged = 0;
+        } else {
+            i += 1;
+            unchanged += 1;
+        }
+    }
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::boolean::*;
+    use crate::base::Point;
+    use crate::collision::BoundingBox;
+    use crate::shape::{Circle, Polygon, PolygonWithHoles, Shape};
+
+    fn assert_area(result: &MultiPolygon, expected: f32) {
+        assert!(
+            (result.area() - expected).abs() < 1e-3,
+            "{} == {}",
+            result.area(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_overlapping_squares() {
+        let a = Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners());
+        let b = Polygon::new(&BoundingBox::from_edges(1.0, 1.0, 3.0, 3.0).corners());
+        let result = union(a.clone(), b.clone()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.polygons[0].outer.vertices.len(), 8);
+        assert_area(&result, 7.0);
+        let result = intersection(a.clone(), b.clone()).unwrap();
+        assert_eq!(result.polygons[0].outer.vertices.len(), 4);
+        assert_area(&result, 1.0);
+        assert_area(&difference(a.clone(), b.clone()).unwrap(), 3.0);
+        let result = xor(a, b).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_area(&result, 6.0);
+    }
+    #[test]
+    fn test_coincident_edges() {
+        let result = union(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(2.0, 0.0, 4.0, 2.0).corners()),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.polygons[0].outer.vertices.len(), 4);
+        assert_area(&result, 8.0);
+        // partially shared edge
+        let result = union(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(2.0, 1.0, 4.0, 3.0).corners()),
+        )
+        .unwrap();
+        assert_eq!(result.polygons[0].outer.vertices.len(), 8);
+        assert_area(&result, 8.0);
+        assert!(intersection(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(2.0, 0.0, 4.0, 2.0).corners())
+        )
+        .unwrap()
+        .is_empty());
+    }
+    #[test]
+    fn test_touching_vertices() {
+        let result = union(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 1.0, 1.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(1.0, 1.0, 2.0, 2.0).corners()),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 2);
+        assert!(result
+            .polygons
+            .iter()
+            .all(|polygon| polygon.outer.vertices.len() == 4));
+        // a checkerboard only touches at corners, every ring edge has to go in
+        let mut board = MultiPolygon::default();
+        for i in 0..4 {
+            for j in 0..4 {
+                if (i + j) % 2 == 0 {
+                    let cell = Polygon::new(
+                        &BoundingBox::from_edges(
+                            i as f32,
+                            j as f32,
+                            i as f32 + 1.0,
+                            j as f32 + 1.0,
+                        )
+                        .corners(),
+                    );
+                    board = union(board, cell).unwrap();
+                }
+            }
+        }
+        assert_eq!(board.len(), 8);
+        assert_area(&board, 8.0);
+    }
+    #[test]
+    fn test_holes() {
+        let result = difference(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners()),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.polygons[0].holes.len(), 1);
+        assert!(result.polygons[0].holes[0].signed_area() < 0.0);
+        assert_area(&result, 96.0);
+        // filling the hole again
+        let result = union(
+            result,
+            Polygon::new(&BoundingBox::from_edges(3.0, 3.0, 7.0, 7.0).corners()),
+        )
+        .unwrap();
+        assert!(result.polygons[0].holes.is_empty());
+        assert_area(&result, 100.0);
+        // an island inside the hole stays a separate polygon
+        let ring = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(2.0, 2.0, 8.0, 8.0).corners(),
+            )],
+        );
+        let result = union(
+            ring,
+            Polygon::new(&BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners()),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_area(&result, 64.0 + 4.0);
+    }
+    #[test]
+    fn test_split() {
+        let result = difference(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 2.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(4.0, -1.0, 6.0, 3.0).corners()),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_area(&result, 16.0);
+    }
+    #[test]
+    fn test_concave() {
+        let u_shape = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(6.0, 6.0),
+            Point::new(4.0, 6.0),
+            Point::new(4.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 6.0),
+            Point::new(0.0, 6.0),
+        ]);
+        let bar = Polygon::new(&BoundingBox::from_edges(-1.0, 4.0, 7.0, 5.0).corners());
+        let result = intersection(u_shape.clone(), bar.clone()).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_area(&result, 4.0);
+        let result = union(u_shape, bar).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.polygons[0].holes.len(), 1);
+        assert_area(&result, 28.0 + 8.0 - 4.0);
+    }
+    #[test]
+    fn test_crater() {
+        let terrain = Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 20.0, 10.0).corners());
+        let crater = Circle::new(Point::new(10.0, 10.0), 3.0).polygon();
+        let result = difference(terrain.clone(), crater.clone()).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_area(&result, 200.0 - crater.area() / 2.0);
+        assert!(!result.is_inside(Point::new(10.0, 9.0)));
+    }
+    #[test]
+    fn test_resolve() {
+        // two counter-clockwise rooms and a clockwise copy of the first one
+        let mut reversed = Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 4.0, 4.0).corners());
+        reversed.vertices.reverse();
+        let rings = vec![
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 4.0, 4.0).corners()),
+            Polygon::new(&BoundingBox::from_edges(2.0, 2.0, 6.0, 6.0).corners()),
+            reversed,
+        ];
+        assert_area(&resolve(&rings[..2], FillRule::NonZero).unwrap(), 28.0);
+        assert_area(&resolve(&rings[..2], FillRule::EvenOdd).unwrap(), 24.0);
+        // the clockwise copy cancels the first room except where it overlaps the second
+        assert_area(&resolve(&rings, FillRule::Positive).unwrap(), 16.0);
+        assert!(resolve(&rings, FillRule::Negative).unwrap().is_empty());
+        // a bow tie splits at its crossing
+        let bow_tie = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.0, 2.0),
+        ]);
+        let result = resolve(&[bow_tie], FillRule::NonZero).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_area(&result, 2.0);
+    }
+}
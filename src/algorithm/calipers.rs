@@ -0,0 +1,447 @@
+use crate::algorithm::hull::convex_hull;
+use crate::algorithm::validation::orient;
+use crate::base::{Angle, Point, Size, Vector};
+use crate::shape::Rectangle;
+
+pub fn minimum_area_rectangle(points: &[Point]) -> Rectangle {
+    smallest_rectangle(points, |size| size.w * size.h)
+}
+
+pub fn minimum_perimeter_rectangle(points: &[Point]) -> Rectangle {
+    smallest_rectangle(points, |size| size.w + size.h)
+}
+
+// The optimal rectangle has a side on a hull edge. Rotating calipers track the extreme
+// vertices in the edge direction and across it, so each edge is measured in constant time.
+fn smallest_rectangle(points: &[Point], measure: impl Fn(Size) -> f32) -> Rectangle {
+    let hull = convex_hull(points).vertices;
+    let n = hull.len();
+    match n {
+        0 => return Rectangle::default(),
+        1 => return Rectangle::new(hull[0], Size::zero(), Angle::zero()),
+        _ => (),
+    }
+    let along = |i: usize, u: Vector| hull[i % n].to_vector().dot(u);
+    let (mut right, mut top, mut left) = (1, 1, 1);
+    let mut best = (f32::INFINITY, Rectangle::default());
+    for i in 0..n {
+        let u = Vector::from_points(hull[i], hull[(i + 1) % n]).get_unit_vector();
+        // the hull is counter-clockwise, its inside is to the left of every edge
+        let v = u.get_normal_vector();
+        right = right.max(i + 1);
+        while along(right + 1, u) > along(right, u) {
+            right += 1;
+        }
+        top = top.max(right);
+        while along(top + 1, v) > along(top, v) {
+            top += 1;
+        }
+        left = left.max(top);
+        while along(left + 1, u) < along(left, u) {
+            left += 1;
+        }
+        let base = hull[i].to_vector();
+        let (min_u, max_u) = (along(left, u), along(right, u));
+        let (min_v, max_v) = (base.dot(v), along(top, v));
+        let size = Size::new(max_u - min_u, max_v - min_v);
+        let score = measure(size);
+        if score < best.0 {
+            let center = u * ((min_u + max_u) / 2.0) + v * ((min_v + max_v) / 2.0);
+            best = (
+                score,
+                Rectangle::new(center.to_point(), size, u.orientation()),
+            );
+        }
+    }
+    best.1
+}
+
+// Every pair of hull vertices that admits parallel supporting lines, each pair once.
+pub fn antipodal_pairs(points: &[Point]) -> Vec<(Point, Point)> {
+    let hull = convex_hull(points).vertices;
+    let n = hull.len();
+    if n < 3 {
+        return if n == 2 {
+            vec![(hull[0], hull[1])]
+        } else {
+            Vec::new()
+        };
+    }
+    let mut pairs = Vec::new();
+    let mut j = 1;
+    for i in 0..n {
+        let (a, b) = (hull[i], hull[(i + 1) % n]);
+        let height = |k: usize| orient(a, b, hull[k % n]);
+        j = j.max(i + 1);
+        while height(j + 1) > height(j) {
+            j += 1;
+        }
+        pairs.push((i, j % n));
+        pairs.push(((i + 1) % n, j % n));
+        // an edge parallel to this one makes both its vertices antipodal to both of ours
+        if height(j + 1) >= height(j) - EPSILON * height(j) {
+            pairs.push((i, (j + 1) % n));
+            pairs.push(((i + 1) % n, (j + 1) % n));
+        }
+    }
+    let mut pairs: Vec<(usize, usize)> = pairs
+        .into_iter()
+        .filter(|&(a, b)| a != b)
+        .map(|(a, b)| (a.min(b), a.max(b)))
+        .collect();
+    pairs.sort_unstable();
+    pairs.dedup();
+    pairs.into_iter().map(|(a, b)| (hull[a], hull[b])).collect()
+}
+
+// farthest pair of points
+pub fn diameter(points: &[Point]) -> (f32, Point, Point) {
+    antipodal_pairs(points)
+        .into_iter()
+        .map(|(a, b)| (a.distance_to(b), a, b))
+        .fold((0.0, Point::zero(), Point::zero()), |best, candidate| {
+            if candidate.0 > best.0 {
+                candidate
+            } else {
+                best
+            }
+        })
+}
+
+// Narrowest gap between two parallel lines enclosing the points, and the unit
+// direction across it.
+pub fn width(points: &[Point]) -> (f32, Vector) {
+    let hull = convex_hull(points).vertices;
+    let n = hull.len();
+    if n < 2 {
+        return (0.0, Vector::new(0.0, 1.0));
+    }
+    let mut best = (f32::INFINITY, Vector::zero());
+    let mut j = 1;
+    for i in 0..n {
+        let (a, b) = (hull[i], hull[(i + 1) % n]);
+        let height = |k: usize| orient(a, b, hull[k % n]);
+        j = j.max(i + 1);
+        while height(j + 1) > height(j) {
+            j += 1;
+        }
+        let width = (height(j) / a.distance_to(b) as f64) as f32;
+        if width < best.0 {
+            let across = Vector::from_points(a, b)
+                .get_normal_vector()
+                .get_unit_vector();
+            best = (width, across);
+        }
+    }
+    best
+}
+
+// The farthest points of two convex polygons are always vertices of their hulls, and
+// the farthest pair is a corner of the hull of their difference.
+pub fn max_distance(a: &[Point], b: &[Point]) -> (f32, Point, Point) {
+    let (hull_a, hull_b) = (convex_hull(a).vertices, convex_hull(b).vertices);
+    difference_corners(&hull_a, &hull_b)
+        .into_iter()
+        .map(|(i, j)| (hull_a[i].distance_to(hull_b[j]), hull_a[i], hull_b[j]))
+        .fold((0.0, Point::zero(), Point::zero()), |best, candidate| {
+            if candidate.0 > best.0 {
+                candidate
+            } else {
+                best
+            }
+        })
+}
+
+// Zero with a shared point if the convex polygons touch or overlap, otherwise the gap
+// between them and the closest point on each. The gap is the distance from the origin
+// to the hull of a - b, whose every edge is an edge of one polygon offset by a corner
+// of the other.
+pub fn min_distance(a: &[Point], b: &[Point]) -> (f32, Point, Point) {
+    let (hull_a, hull_b) = (convex_hull(a).vertices, convex_hull(b).vertices);
+    if hull_a.is_empty() || hull_b.is_empty() {
+        return (0.0, Point::zero(), Point::zero());
+    }
+    let corners = difference_corners(&hull_a, &hull_b);
+    if let Some(point) = common_point(&hull_a, &hull_b, &corners) {
+        return (0.0, point, point);
+    }
+    let n = corners.len();
+    let mut best = (f32::INFINITY, Point::zero(), Point::zero());
+    for k in 0..n {
+        let ((i, j), (next_i, next_j)) = (corners[k], corners[(k + 1) % n]);
+        let (p, q) = (hull_a[i], hull_b[j]);
+        let (step_p, step_q) = (
+            Vector::from_points(p, hull_a[next_i]),
+            Vector::from_points(q, hull_b[next_j]),
+        );
+        let (start, along) = (Vector::from_points(q, p), step_p - step_q);
+        let t = if along.squared_magnitude() > 0.0 {
+            (-start.dot(along) / along.squared_magnitude()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (p, q) = (p + step_p * t, q + step_q * t);
+        if p.distance_to(q) < best.0 {
+            best = (p.distance_to(q), p, q);
+        }
+    }
+    best
+}
+
+const EPSILON: f64 = 1e-9;
+
+// Corners of the hull of a - b as pairs of indices into the two counter-clockwise
+// hulls. Starting from the lowest corner of each, the edges of a and of the mirrored b
+// are merged by direction, so both outlines are walked once.
+fn difference_corners(a: &[Point], b: &[Point]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return Vec::new();
+    }
+    let mirrored: Vec<Point> = b.iter().map(|p| Point::new(-p.x, -p.y)).collect();
+    let (start_a, start_b) = (lowest(a), lowest(&mirrored));
+    let edge = |points: &[Point], k: usize| {
+        let len = points.len();
+        Vector::from_points(points[k % len], points[(k + 1) % len])
+    };
+    let (edges_a, edges_b) = (if n > 1 { n } else { 0 }, if m > 1 { m } else { 0 });
+    let (mut i, mut j) = (0, 0);
+    let mut corners = vec![(start_a, start_b)];
+    while i < edges_a || j < edges_b {
+        let turn = if i == edges_a {
+            -1.0
+        } else if j == edges_b {
+            1.0
+        } else {
+            edge(a, start_a + i).cross(edge(&mirrored, start_b + j))
+        };
+        if turn >= 0.0 {
+            i += 1;
+        }
+        if turn <= 0.0 {
+            j += 1;
+        }
+        if i < edges_a || j < edges_b {
+            corners.push(((start_a + i) % n, (start_b + j) % m));
+        }
+    }
+    corners
+}
+
+fn lowest(points: &[Point]) -> usize {
+    (0..points.len())
+        .min_by(|&i, &k| {
+            let (p, q) = (points[i], points[k]);
+            p.y.partial_cmp(&q.y)
+                .unwrap()
+                .then(p.x.partial_cmp(&q.x).unwrap())
+        })
+        .unwrap()
+}
+
+// If the origin lies in the hull of a - b it is a weighted sum of three of its corners,
+// and the same weights on the matching corners of a give a point of both polygons.
+fn common_point(a: &[Point], b: &[Point], corners: &[(usize, usize)]) -> Option<Point> {
+    let n = corners.len();
+    if n < 3 {
+        return None;
+    }
+    let corner = |k: usize| {
+        let (i, j) = corners[k % n];
+        Vector::from_points(b[j], a[i]).to_point()
+    };
+    let origin = Point::zero();
+    if (0..n).any(|k| orient(corner(k), corner(k + 1), origin) < 0.0) {
+        return None;
+    }
+    for k in 1..n - 1 {
+        let weights = [
+            orient(corner(k), corner(k + 1), origin),
+            orient(corner(k + 1), corner(0), origin),
+            orient(corner(0), corner(k), origin),
+        ];
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 || weights.iter().any(|&w| w < -EPSILON * total) {
+            continue;
+        }
+        let point = [0, k, k + 1]
+            .iter()
+            .zip(weights.iter())
+            .fold(Vector::zero(), |sum, (&k, &w)| {
+                sum + a[corners[k].0].to_vector() * (w / total) as f32
+            });
+        return Some(point.to_point());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::calipers::*;
+    use crate::base::{Angle, Line, Point, Size, Vector};
+    use crate::shape::{Hexagon, PointLocation, Polygon, Rectangle, Shape};
+
+    // every corner against every corner and edge of the other hull
+    fn brute_force(a: &[Point], b: &[Point]) -> (f32, f32) {
+        let (a, b) = (convex_hull(a).vertices, convex_hull(b).vertices);
+        let (mut near, mut far) = (f32::INFINITY, 0f32);
+        for (from, to) in [(&a, &b), (&b, &a)] {
+            for &p in from.iter() {
+                for (k, &q) in to.iter().enumerate() {
+                    far = far.max(p.distance_to(q));
+                    let edge = Line::new(q, to[(k + 1) % to.len()]);
+                    near = near.min(p.distance_to(edge.closest_point(p)));
+                }
+            }
+        }
+        (near, far)
+    }
+
+    #[test]
+    fn test_rotated_rectangle() {
+        let rectangle =
+            Rectangle::new(Point::new(3.0, -2.0), Size::new(4.0, 1.0), Angle::new(30.0));
+        let points = rectangle.polygon().vertices;
+        for fitted in [
+            minimum_area_rectangle(&points),
+            minimum_perimeter_rectangle(&points),
+        ] {
+            assert!((fitted.area() - 4.0).abs() < 1e-4);
+            assert_eq!(fitted.center(), rectangle.center());
+            let size = fitted.size();
+            assert!((size.max() - 4.0).abs() < 1e-4 && (size.min() - 1.0).abs() < 1e-4);
+            let mut corners = fitted.polygon().vertices;
+            for point in points.iter() {
+                let idx = corners
+                    .iter()
+                    .position(|corner| corner.distance_to(*point) < 1e-4)
+                    .unwrap();
+                corners.remove(idx);
+            }
+        }
+    }
+    #[test]
+    fn test_point_set() {
+        let points: Vec<Point> = (0..20)
+            .map(|i| {
+                let t = i as f32 * 0.7;
+                Point::new(2.0 * t.cos() + 0.1 * t, t.sin())
+            })
+            .collect();
+        let fitted = minimum_area_rectangle(&points);
+        let polygon = fitted.polygon();
+        for &point in points.iter() {
+            let outside =
+                polygon.closest_point(point).distance_to(point) > 1e-4 && !polygon.is_inside(point);
+            assert!(!outside);
+        }
+        let aabb = Polygon::new(&points).to_bounding_box();
+        assert!(fitted.area() <= (aabb.x2() - aabb.x1()) * (aabb.y2() - aabb.y1()));
+        assert_eq!(minimum_area_rectangle(&[]), Rectangle::default());
+        let segment = minimum_area_rectangle(&[Point::new(0.0, 0.0), Point::new(0.0, 3.0)]);
+        assert_eq!(segment.center(), Point::new(0.0, 1.5));
+        assert_eq!(segment.area(), 0.0);
+    }
+    #[test]
+    fn test_antipodal_pairs() {
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        // with parallel sides every pair of corners is antipodal, neighbours included
+        assert_eq!(antipodal_pairs(&square).len(), 6);
+        let triangle = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(1.0, 3.0),
+        ];
+        assert_eq!(antipodal_pairs(&triangle).len(), 3);
+        let (length, a, b) = diameter(&square);
+        assert!((length - 8f32.sqrt()).abs() < 1e-6);
+        assert_eq!(a.distance_to(b), length);
+        assert_eq!(diameter(&triangle).0, 18f32.sqrt());
+    }
+    #[test]
+    fn test_width() {
+        let rectangle = Rectangle::new(Point::new(1.0, 1.0), Size::new(5.0, 2.0), Angle::new(60.0));
+        let (narrowest, direction) = width(&rectangle.polygon().vertices);
+        assert!((narrowest - 2.0).abs() < 1e-4);
+        let expected = Vector::new(-(60f32.to_radians().sin()), 60f32.to_radians().cos());
+        assert!((direction.dot(expected).abs() - 1.0).abs() < 1e-4);
+        let points = [Point::new(0.0, 0.0), Point::new(3.0, 4.0)];
+        assert_eq!(width(&points).0, 0.0);
+        assert_eq!(diameter(&points).0, 5.0);
+    }
+    #[test]
+    fn test_distances() {
+        let a = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let b = [
+            Point::new(4.0, 1.0),
+            Point::new(6.0, 0.0),
+            Point::new(6.0, 3.0),
+        ];
+        let (gap, p, q) = min_distance(&a, &b);
+        assert_eq!(gap, 2.0);
+        assert_eq!(p, Point::new(2.0, 1.0));
+        assert_eq!(q, Point::new(4.0, 1.0));
+        let (gap, p, q) = min_distance(&b, &a);
+        assert_eq!(
+            (gap, p, q),
+            (2.0, Point::new(4.0, 1.0), Point::new(2.0, 1.0))
+        );
+        let (far, p, q) = max_distance(&a, &b);
+        assert_eq!(far, 45f32.sqrt());
+        assert_eq!((p, q), (Point::new(0.0, 0.0), Point::new(6.0, 3.0)));
+        // crossing bars without a vertex inside the other still overlap
+        let horizontal = [
+            Point::new(-3.0, -1.0),
+            Point::new(3.0, -1.0),
+            Point::new(3.0, 1.0),
+            Point::new(-3.0, 1.0),
+        ];
+        let vertical: Vec<Point> = horizontal.iter().map(|p| Point::new(p.y, p.x)).collect();
+        assert_eq!(min_distance(&horizontal, &vertical).0, 0.0);
+        let (point, segment) = (
+            [Point::new(1.0, 3.0)],
+            [Point::zero(), Point::new(4.0, 0.0)],
+        );
+        assert_eq!(
+            min_distance(&point, &segment),
+            (3.0, Point::new(1.0, 3.0), Point::new(1.0, 0.0))
+        );
+        assert_eq!(max_distance(&point, &segment).0, 18f32.sqrt());
+    }
+    #[test]
+    fn test_distances_against_brute_force() {
+        let hexagon = Hexagon::new(Point::zero(), 2.0, Angle::new(10.0))
+            .polygon()
+            .vertices;
+        let outline = Polygon::new(&hexagon);
+        for step in 0..24 {
+            let angle = Angle::new(step as f64 * 15.0);
+            for &reach in [1.0, 3.0, 6.0].iter() {
+                let center = Point::zero() + Vector::from_orientation(angle) * reach;
+                let rectangle =
+                    Rectangle::new(center, Size::new(3.0, 1.0), Angle::new(step as f64 * 7.0));
+                let other = rectangle.polygon().vertices;
+                let (near, far) = brute_force(&hexagon, &other);
+                assert!((max_distance(&hexagon, &other).0 - far).abs() < 1e-4);
+                let (gap, p, q) = min_distance(&hexagon, &other);
+                if gap > 0.0 {
+                    assert!((gap - near).abs() < 1e-4);
+                    assert!((p.distance_to(q) - gap).abs() < 1e-6);
+                } else {
+                    assert_ne!(outline.locate(p), PointLocation::Outside);
+                    assert_ne!(rectangle.polygon().locate(p), PointLocation::Outside);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use crate::algorithm::triangulation::triangulate;
+use crate::algorithm::validation::orient;
+use crate::base::Point;
+use crate::shape::Polygon;
+
+// Bayazit splits recursively and leaves pieces nested deeper than this to Hertel-Mehlhorn
+const MAX_DEPTH: usize = 100;
+
+// Triangulates and then drops every diagonal whose removal keeps both sides convex.
+// Uses only the original vertices and gives at most four times the optimal count.
+pub fn hertel_mehlhorn(vertices: &[Point]) -> Vec<Polygon> {
+    let ring = counter_clockwise(vertices);
+    let mut pieces: Vec<Option<Vec<usize>>> = triangulate(&ring)
+        .into_iter()
+        .map(|triangle| Some(triangle.to_vec()))
+        .collect();
+    let mut owner = HashMap::new();
+    for (id, piece) in pieces.iter().enumerate() {
+        let piece = piece.as_ref().unwrap();
+        for k in 0..3 {
+            owner.insert((piece[k], piece[(k + 1) % 3]), id);
+        }
+    }
+    let mut diagonals: Vec<(usize, usize)> = owner
+        .keys()
+        .copied()
+        .filter(|&(a, b)| a < b && owner.contains_key(&(b, a)))
+        .collect();
+    diagonals.sort_unstable();
+    for (a, b) in diagonals {
+        let (p, q) = (owner[&(a, b)], owner[&(b, a)]);
+        if p == q {
+            continue;
+        }
+        // walk p from b round to a, then q from a round to b
+        let first = rotated(pieces[p].as_ref().unwrap(), b);
+        let second = rotated(pieces[q].as_ref().unwrap(), a);
+        let mut merged = first.clone();
+        merged.extend_from_slice(&second[1..second.len() - 1]);
+        let convex_at_a = orient(ring[first[first.len() - 2]], ring[a], ring[second[1]]) >= 0.0;
+        let convex_at_b = orient(ring[second[second.len() - 2]], ring[b], ring[first[1]]) >= 0.0;
+        if !(convex_at_a && convex_at_b) {
+            continue;
+        }
+        owner.remove(&(a, b));
+        owner.remove(&(b, a));
+        for k in 0..merged.len() {
+            owner.insert((merged[k], merged[(k + 1) % merged.len()]), p);
+        }
+        pieces[p] = Some(merged);
+        pieces[q] = None;
+    }
+    pieces
+        .into_iter()
+        .flatten()
+        .map(|piece| Polygon {
+            vertices: piece.into_iter().map(|idx| ring[idx]).collect(),
+        })
+        .collect()
+}
+
+// Splits at each reflex vertex towards the closest visible vertex in its wedge, or a
+// new point halfway between where the wedge leaves the polygon. Usually gives fewer
+// pieces than `hertel_mehlhorn`, but may add vertices.
+pub fn bayazit(vertices: &[Point]) -> Vec<Polygon> {
+    let mut result = Vec::new();
+    let ring = counter_clockwise(vertices);
+    if ring.len() >= 3 {
+        split(&ring, 0, &mut result);
+    }
+    result
+}
+
+fn split(polygon: &[Point], depth: usize, result: &mut Vec<Polygon>) {
+    let n = polygon.len();
+    let at = |i: usize| polygon[i % n];
+    let reflex = (0..n).find(|&i| orient(at(i + n - 1), at(i), at(i + 1)) < 0.0);
+    let i = match reflex {
+        Some(i) if depth < MAX_DEPTH => i,
+        Some(_) => {
+            result.extend(hertel_mehlhorn(polygon));
+            return;
+        }
+        None => {
+            result.push(Polygon {
+                vertices: polygon.to_vec(),
+            });
+            return;
+        }
+    };
+    let (prev, point, next) = (at(i + n - 1), at(i), at(i + 1));
+    // where the extensions of both edges at the reflex vertex hit the outline first
+    let mut lower = (f32::INFINITY, Point::zero(), 0);
+    let mut upper = (f32::INFINITY, Point::zero(), 0);
+    for j in 0..n {
+        let (start, end) = (at(j + n - 1), at(j));
+        if orient(prev, point, end) > 0.0 && orient(prev, point, start) <= 0.0 {
+            if let Some(hit) = line_intersection(prev, point, end, start) {
+                let distance = point.squared_distance_to(hit);
+                if orient(next, point, hit) < 0.0 && distance < lower.0 {
+                    lower = (distance, hit, j);
+                }
+            }
+        }
+        let (start, end) = (at(j), at(j + 1));
+        if orient(next, point, end) > 0.0 && orient(next, point, start) <= 0.0 {
+            if let Some(hit) = line_intersection(next, point, start, end) {
+                let distance = point.squared_distance_to(hit);
+                if orient(prev, point, hit) > 0.0 && distance < upper.0 {
+                    upper = (distance, hit, j);
+                }
+            }
+        }
+    }
+    if !lower.0.is_finite() || !upper.0.is_finite() {
+        // a wedge ray found no edge, there is nothing sound to cut to
+        result.extend(hertel_mehlhorn(polygon));
+        return;
+    }
+    let (lower_index, upper_index) = (lower.2, upper.2);
+    let (mut first, mut second) = (Vec::new(), Vec::new());
+    let candidates = if lower_index > upper_index {
+        upper_index + n
+    } else {
+        upper_index
+    };
+    // with no vertex between the two hit edges the wedge only reaches the outline
+    let closest = if lower_index == (upper_index + 1) % n {
+        None
+    } else {
+        (lower_index..=candidates)
+            .map(|j| j % n)
+            .filter(|&j| {
+                j != i
+                    && orient(prev, point, at(j)) >= 0.0
+                    && orient(next, point, at(j)) <= 0.0
+                    && can_see(polygon, i, j)
+            })
+            .min_by(|&a, &b| {
+                let da = point.squared_distance_to(at(a));
+                let db = point.squared_distance_to(at(b));
+                da.partial_cmp(&db).unwrap()
+            })
+    };
+    match closest {
+        Some(j) => {
+            // cut along the diagonal from i to j
+            first.extend(walk(polygon, i, j));
+            second.extend(walk(polygon, j, i));
+        }
+        None => {
+            // no vertex to connect to, cut to a new point on the outline instead
+            let steiner = (lower.1 + upper.1) / 2.0;
+            first.extend(walk(polygon, i, upper_index));
+            first.push(steiner);
+            second.push(steiner);
+            second.extend(walk(polygon, lower_index, i));
+        }
+    }
+    if first.len() < 3 || second.len() < 3 {
+        result.extend(hertel_mehlhorn(polygon));
+        return;
+    }
+    split(&first, depth + 1, result);
+    split(&second, depth + 1, result);
+}
+
+// vertices from `from` to `to` inclusive, going round the end if needed
+fn walk(polygon: &[Point], from: usize, to: usize) -> Vec<Point> {
+    let n = polygon.len();
+    let count = (to + n - from) % n + 1;
+    (0..count).map(|k| polygon[(from + k) % n]).collect()
+}
+
+// true if the diagonal from i to j stays clear of every edge not ending in i or j
+fn can_see(polygon: &[Point], i: usize, j: usize) -> bool {
+    let n = polygon.len();
+    (0..n).all(|k| {
+        let l = (k + 1) % n;
+        if k == i || k == j || l == i || l == j {
+            return true;
+        }
+        let (a, b) = (polygon[i], polygon[j]);
+        let (c, d) = (polygon[k], polygon[l]);
+        let (d1, d2) = (orient(c, d, a), orient(c, d, b));
+        let (d3, d4) = (orient(a, b, c), orient(a, b, d));
+        !(d1 * d2 <= 0.0 && d3 * d4 <= 0.0)
+    })
+}
+
+fn line_intersection(a: Point, b: Point, c: Point, d: Point) -> Option<Point> {
+    let denominator = cross_vectors(b - a, d - c);
+    if denominator == 0.0 {
+        return None;
+    }
+    let t = cross_vectors(c - a, d - c) / denominator;
+    Some(Point::new(
+        (a.x as f64 + t * (b.x as f64 - a.x as f64)) as f32,
+        (a.y as f64 + t * (b.y as f64 - a.y as f64)) as f32,
+    ))
+}
+
+fn cross_vectors(u: Point, v: Point) -> f64 {
+    u.x as f64 * v.y as f64 - u.y as f64 * v.x as f64
+}
+
+fn rotated(piece: &[usize], start: usize) -> Vec<usize> {
+    let offset = piece.iter().position(|&idx| idx == start).unwrap();
+    piece[offset..]
+        .iter()
+        .chain(piece[..offset].iter())
+        .copied()
+        .collect()
+}
+
+fn counter_clockwise(vertices: &[Point]) -> Vec<Point> {
+    let mut ring: Vec<Point> = Vec::with_capacity(vertices.len());
+    for &vertex in vertices.iter() {
+        if ring.last() != Some(&vertex) {
+            ring.push(vertex);
+        }
+    }
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    Polygon::new_counter_clockwise(&ring).vertices
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::decomposition::*;
+    use crate::base::Point;
+
+    fn comb() -> Vec<Point> {
+        // three teeth pointing up from a bar
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.0),
+            Point::new(5.0, 3.0),
+            Point::new(4.0, 3.0),
+            Point::new(4.0, 1.0),
+            Point::new(3.0, 1.0),
+            Point::new(3.0, 3.0),
+            Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 3.0),
+            Point::new(0.0, 3.0),
+        ]
+    }
+
+    fn star(points: usize) -> Vec<Point> {
+        (0..2 * points)
+            .map(|i| {
+                let t = i as f32 * std::f32::consts::PI / points as f32;
+                let r = if i % 2 == 0 { 5.0 } else { 2.0 };
+                Point::new(r * t.cos(), r * t.sin())
+            })
+            .collect()
+    }
+
+    fn assert_decomposition(pieces: &[Polygon], area: f32) {
+        let total: f32 = pieces.iter().map(|piece| piece.area()).sum();
+        assert!((total - area).abs() < 1e-3, "{} == {}", total, area);
+        for piece in pieces.iter() {
+            let report = piece.validate();
+            assert!(report.is_convex, "{}", piece);
+            assert!(piece.signed_area() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_hertel_mehlhorn() {
+        let pieces = hertel_mehlhorn(&comb());
+        assert_decomposition(&pieces, 11.0);
+        assert!(pieces.len() <= 6);
+        let pieces = hertel_mehlhorn(&star(5));
+        assert_decomposition(&pieces, Polygon::new(&star(5)).area());
+        assert!(pieces.len() <= 6);
+        // convex input stays in one piece, whichever way it winds
+        let mut square = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        square.reverse();
+        assert_eq!(hertel_mehlhorn(&square).len(), 1);
+    }
+    #[test]
+    fn test_bayazit() {
+        let pieces = bayazit(&comb());
+        assert_decomposition(&pieces, 11.0);
+        assert!(pieces.len() <= 5);
+        let pieces = bayazit(&star(5));
+        assert_decomposition(&pieces, Polygon::new(&star(5)).area());
+        assert!(pieces.len() <= 6);
+        assert_eq!(bayazit(&star(5)[..2]).len(), 0);
+    }
+    #[test]
+    fn test_steiner_point() {
+        // the notch tip sees no vertex in its wedge, the cut ends on the opposite edge
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(2.5, 4.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.5, 4.0),
+            Point::new(0.0, 4.0),
+        ];
+        let pieces = bayazit(&vertices);
+        assert_decomposition(&pieces, 15.0);
+        assert_eq!(pieces.len(), 2);
+    }
+    #[test]
+    fn test_depth_limit() {
+        // past the depth limit the rest still comes out convex
+        let mut pieces = Vec::new();
+        split(&comb(), MAX_DEPTH, &mut pieces);
+        assert_decomposition(&pieces, 11.0);
+        assert!(pieces.len() > 1);
+    }
+}
@@ -0,0 +1,828 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::algorithm::validation::orient;
+use crate::base::{Line, Point, Triangle};
+use crate::shape::Polygon;
+
+// Every hull edge is closed off by a ghost triangle towards this vertex at infinity,
+// so each face always has three neighbours and points outside the hull need no super triangle.
+const GHOST: usize = usize::MAX;
+// relative distance under which a point counts as lying on a segment
+const EPSILON: f64 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Face {
+    vertices: [usize; 3],
+    // neighbors[i] lies across the edge opposite vertices[i]
+    neighbors: [usize; 3],
+    alive: bool,
+}
+
+impl Face {
+    fn is_ghost(&self) -> bool {
+        self.vertices.contains(&GHOST)
+    }
+    fn index_of(&self, vertex: usize) -> Option<usize> {
+        self.vertices.iter().position(|&v| v == vertex)
+    }
+    fn index_of_neighbor(&self, neighbor: usize) -> usize {
+        self.neighbors.iter().position(|&n| n == neighbor).unwrap()
+    }
+    fn replace_neighbor(&mut self, old: usize, new: usize) {
+        for neighbor in self.neighbors.iter_mut() {
+            if *neighbor == old {
+                *neighbor = new;
+                return;
+            }
+        }
+    }
+}
+
+// Triangle ids index into the face storage and are only stable until the next mutation.
+#[derive(Clone, Debug, Default)]
+pub struct Triangulation {
+    points: Vec<Point>,
+    faces: Vec<Face>,
+    free: Vec<usize>,
+    vertex_face: Vec<usize>,
+    constraints: HashSet<(usize, usize)>,
+    pending_constraints: Vec<(usize, usize)>,
+    last: usize,
+}
+
+#[allow(dead_code)]
+impl Triangulation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn from_points(points: &[Point]) -> Self {
+        let mut triangulation = Self::new();
+        for &point in points.iter() {
+            triangulation.insert(point);
+        }
+        triangulation
+    }
+    pub fn from_polygon(polygon: &Polygon) -> Self {
+        let mut triangulation = Self::new();
+        triangulation.insert_polygon(polygon);
+        triangulation
+    }
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+    pub fn triangle_count(&self) -> usize {
+        self.triangle_ids().count()
+    }
+    pub fn triangle_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(|(_, face)| face.alive && !face.is_ghost())
+            .map(|(id, _)| id)
+    }
+    pub fn triangles(&self) -> Vec<Triangle> {
+        self.triangle_ids().map(|id| self.triangle(id)).collect()
+    }
+    pub fn triangle(&self, id: usize) -> Triangle {
+        let [a, b, c] = self.vertices(id);
+        Triangle::new(self.points[a], self.points[b], self.points[c])
+    }
+    // counter-clockwise vertex indices of a triangle
+    pub fn vertices(&self, id: usize) -> [usize; 3] {
+        self.faces[id].vertices
+    }
+    // neighbours across the edges opposite each vertex, None on the convex hull
+    pub fn neighbors(&self, id: usize) -> [Option<usize>; 3] {
+        let mut result = [None; 3];
+        for (k, &neighbor) in self.faces[id].neighbors.iter().enumerate() {
+            if !self.faces[neighbor].is_ghost() {
+                result[k] = Some(neighbor);
+            }
+        }
+        result
+    }
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::new();
+        for id in self.triangle_ids() {
+            let face = &self.faces[id];
+            for k in 0..3 {
+                let u = face.vertices[(k + 1) % 3];
+                let w = face.vertices[(k + 2) % 3];
+                if u < w || self.faces[face.neighbors[k]].is_ghost() {
+                    edges.push((u, w));
+                }
+            }
+        }
+        edges
+    }
+    pub fn vertex_neighbors(&self, vertex: usize) -> Vec<usize> {
+        if self.faces.is_empty() {
+            return Vec::new();
+        }
+        self.faces_around(vertex)
+            .into_iter()
+            .map(|(f, k)| self.faces[f].vertices[(k + 1) % 3])
+            .filter(|&v| v != GHOST)
+            .collect()
+    }
+    pub fn is_constrained(&self, a: usize, b: usize) -> bool {
+        self.constraints.contains(&edge_key(a, b))
+    }
+    pub fn constraints(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.constraints.iter().copied()
+    }
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        !self.faces.is_empty() && self.find_edge(a, b).is_some()
+    }
+    // id of the triangle containing the point, None outside the convex hull
+    pub fn locate(&self, point: Point) -> Option<usize> {
+        if self.faces.is_empty() {
+            return None;
+        }
+        let face = self.locate_face(point);
+        if self.faces[face].is_ghost() {
+            None
+        } else {
+            Some(face)
+        }
+    }
+    // returns the vertex index, reusing an existing vertex at the same position
+    pub fn insert(&mut self, point: Point) -> usize {
+        if self.faces.is_empty() {
+            if let Some(idx) = self.points.iter().position(|&p| p == point) {
+                return idx;
+            }
+            self.push_point(point);
+            self.initialize();
+            return self.points.len() - 1;
+        }
+        let start = self.locate_face(point);
+        let existing = self.faces[start]
+            .vertices
+            .iter()
+            .copied()
+            .find(|&v| v != GHOST && self.points[v] == point);
+        if let Some(idx) = existing {
+            return idx;
+        }
+        let idx = self.push_point(point);
+        self.insert_vertex(idx, start);
+        idx
+    }
+    // false if the segment could not be forced in, see `add_constraint`
+    pub fn insert_line(&mut self, line: Line) -> bool {
+        let a = self.insert(line.origin);
+        let b = self.insert(line.end);
+        self.add_constraint(a, b)
+    }
+    // false if any side could not be forced in, the others are still added
+    pub fn insert_polygon(&mut self, polygon: &Polygon) -> bool {
+        let indices: Vec<usize> = polygon.vertices.iter().map(|&p| self.insert(p)).collect();
+        let mut added = true;
+        for i in 0..indices.len() {
+            added &= self.add_constraint(indices[i], indices[(i + 1) % indices.len()]);
+        }
+        added
+    }
+    // Forces the segment between two vertices into the triangulation. Vertices lying on
+    // the segment and crossings with other constraints split it into several edges.
+    // Returns false, and records nothing, for a piece that could not be made an edge.
+    // Before the first triangle exists constraints are only queued and count as added.
+    pub fn add_constraint(&mut self, a: usize, b: usize) -> bool {
+        if a == b {
+            return true;
+        }
+        if self.faces.is_empty() {
+            self.pending_constraints.push((a, b));
+            return true;
+        }
+        if self.find_edge(a, b).is_some() {
+            self.constraints.insert(edge_key(a, b));
+            return true;
+        }
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let mut first = None;
+        for (f, k) in self.faces_around(a) {
+            let face = self.faces[f];
+            let u = face.vertices[(k + 1) % 3];
+            let w = face.vertices[(k + 2) % 3];
+            if u == GHOST || w == GHOST {
+                continue;
+            }
+            for &v in [u, w].iter() {
+                if self.on_segment(a, b, self.points[v]) {
+                    return self.add_constraint(a, v) & self.add_constraint(v, b);
+                }
+            }
+            if orient(pa, self.points[u], pb) > 0.0 && orient(pa, self.points[w], pb) < 0.0 {
+                first = Some((f, u, w));
+                break;
+            }
+        }
+        let (mut face, mut right, mut left) = match first {
+            Some(start) => start,
+            None => return false,
+        };
+        // walk along the segment collecting every edge it crosses
+        let mut crossed = VecDeque::new();
+        loop {
+            if self.is_constrained(right, left) {
+                let x = intersection(pa, pb, self.points[right], self.points[left]);
+                let x = self.insert(x);
+                let mut added = true;
+                if x == a || x == b {
+                    // the crossing snapped onto an end point, route the other constraint through it
+                    self.constraints.remove(&edge_key(right, left));
+                    added = self.add_constraint(right, x) & self.add_constraint(x, left);
+                }
+                return added & self.add_constraint(a, x) & self.add_constraint(x, b);
+            }
+            crossed.push_back((right, left));
+            let f = &self.faces[face];
+            let k = (0..3)
+                .find(|&k| f.vertices[k] != right && f.vertices[k] != left)
+                .unwrap();
+            let next = f.neighbors[k];
+            let j = self.faces[next].index_of_neighbor(face);
+            let x = self.faces[next].vertices[j];
+            if x == b || x == GHOST {
+                break;
+            }
+            if self.on_segment(a, b, self.points[x]) {
+                return self.add_constraint(a, x) & self.add_constraint(x, b);
+            }
+            if orient(pa, pb, self.points[x]) > 0.0 {
+                left = x;
+            } else {
+                right = x;
+            }
+            face = next;
+        }
+        // flip crossing edges away, retrying those whose quadrilateral is not convex yet
+        let mut new_edges = Vec::new();
+        let mut budget = 64 * (crossed.len() + 1) * (crossed.len() + 1);
+        while let Some((u, w)) = crossed.pop_front() {
+            if budget == 0 {
+                break;
+            }
+            budget -= 1;
+            let (f, k) = match self.find_edge(u, w) {
+                Some(edge) => edge,
+                None => continue,
+            };
+            let g = self.faces[f].neighbors[k];
+            let p = self.faces[f].vertices[k];
+            let q = self.faces[g].vertices[self.faces[g].index_of_neighbor(f)];
+            if p == GHOST || q == GHOST {
+                continue;
+            }
+            let (pp, pq) = (self.points[p], self.points[q]);
+            if crosses(pp, pq, self.points[u], self.points[w]) {
+                self.flip(f, k);
+                if crosses(pp, pq, pa, pb) {
+                    crossed.push_back((p, q));
+                } else {
+                    new_edges.push((p, q));
+                }
+            } else {
+                crossed.push_back((u, w));
+            }
+        }
+        // the flip budget can run out before the crossings are gone
+        let added = self.find_edge(a, b).is_some();
+        if added {
+            self.constraints.insert(edge_key(a, b));
+        }
+        self.legalize(new_edges);
+        added
+    }
+
+    fn push_point(&mut self, point: Point) -> usize {
+        self.points.push(point);
+        self.vertex_face.push(GHOST);
+        self.points.len() - 1
+    }
+    fn alloc(&mut self, face: Face) -> usize {
+        match self.free.pop() {
+            Some(id) => {
+                self.faces[id] = face;
+                id
+            }
+            None => {
+                self.faces.push(face);
+                self.faces.len() - 1
+            }
+        }
+    }
+    // builds the first triangle once three points are not collinear
+    fn initialize(&mut self) {
+        let n = self.points.len();
+        if n < 3 {
+            return;
+        }
+        let (p0, p1) = (self.points[0], self.points[1]);
+        let c = match (2..n).find(|&c| orient(p0, p1, self.points[c]) != 0.0) {
+            Some(c) => c,
+            None => return,
+        };
+        let (a, b) = if orient(p0, p1, self.points[c]) > 0.0 {
+            (0, 1)
+        } else {
+            (1, 0)
+        };
+        let face = |vertices: [usize; 3], neighbors: [usize; 3]| Face {
+            vertices,
+            neighbors,
+            alive: true,
+        };
+        self.faces = vec![
+            face([a, b, c], [2, 3, 1]),
+            face([b, a, GHOST], [3, 2, 0]),
+            face([c, b, GHOST], [1, 3, 0]),
+            face([a, c, GHOST], [2, 1, 0]),
+        ];
+        self.vertex_face[a] = 0;
+        self.vertex_face[b] = 0;
+        self.vertex_face[c] = 0;
+        self.last = 0;
+        for idx in 0..n {
+            if idx != a && idx != b && idx != c {
+                let start = self.locate_face(self.points[idx]);
+                self.insert_vertex(idx, start);
+            }
+        }
+        for (a, b) in std::mem::take(&mut self.pending_constraints) {
+            self.add_constraint(a, b);
+        }
+    }
+    fn locate_face(&self, point: Point) -> usize {
+        let mut face = if self.faces[self.last].alive && !self.faces[self.last].is_ghost() {
+            self.last
+        } else {
+            self.triangle_ids().next().unwrap_or(0)
+        };
+        let limit = 3 * self.faces.len() + 16;
+        'walk: for step in 0..limit {
+            let f = &self.faces[face];
+            if f.is_ghost() {
+                return face;
+            }
+            // rotating the first edge tested keeps the walk from cycling
+            for j in 0..3 {
+                let k = (j + step) % 3;
+                let u = self.points[f.vertices[(k + 1) % 3]];
+                let w = self.points[f.vertices[(k + 2) % 3]];
+                if orient(u, w, point) < 0.0 {
+                    face = f.neighbors[k];
+                    continue 'walk;
+                }
+            }
+            return face;
+        }
+        // the walk got lost on nearly degenerate input, fall back to a scan
+        (0..self.faces.len())
+            .find(|&id| {
+                let f = &self.faces[id];
+                f.alive
+                    && !f.is_ghost()
+                    && (0..3).all(|k| {
+                        let u = self.points[f.vertices[(k + 1) % 3]];
+                        let w = self.points[f.vertices[(k + 2) % 3]];
+                        orient(u, w, point) >= 0.0
+                    })
+            })
+            .or_else(|| {
+                (0..self.faces.len()).find(|&id| self.faces[id].alive && self.in_circle(id, point))
+            })
+            .unwrap_or(face)
+    }
+    // Bowyer-Watson: remove every face whose circumcircle holds the point and connect
+    // the point to the boundary of the resulting cavity
+    fn insert_vertex(&mut self, idx: usize, start: usize) {
+        let point = self.points[idx];
+        let mut in_cavity = HashSet::new();
+        in_cavity.insert(start);
+        let mut cavity = vec![start];
+        let mut split = Vec::new();
+        let mut i = 0;
+        while i < cavity.len() {
+            let face = self.faces[cavity[i]];
+            i += 1;
+            for k in 0..3 {
+                let g = face.neighbors[k];
+                if in_cavity.contains(&g) {
+                    continue;
+                }
+                let u = face.vertices[(k + 1) % 3];
+                let w = face.vertices[(k + 2) % 3];
+                let include = if u == GHOST || w == GHOST {
+                    self.in_circle(g, point)
+                } else if self.is_constrained(u, w) {
+                    // constraints bound the cavity unless the point splits them
+                    let on_edge = self.on_segment(u, w, point);
+                    if on_edge {
+                        split.push((u, w));
+                    }
+                    on_edge
+                } else {
+                    // only grow through edges facing the point, so the cavity stays
+                    // star shaped and cannot reach around the end of a constraint
+                    let facing = orient(self.points[u], self.points[w], point) > 0.0;
+                    (facing && self.in_circle(g, point)) || self.on_segment(u, w, point)
+                };
+                if include {
+                    in_cavity.insert(g);
+                    cavity.push(g);
+                }
+            }
+        }
+        let mut boundary = Vec::new();
+        for &f in cavity.iter() {
+            let face = self.faces[f];
+            for k in 0..3 {
+                if !in_cavity.contains(&face.neighbors[k]) {
+                    boundary.push((
+                        face.vertices[(k + 1) % 3],
+                        face.vertices[(k + 2) % 3],
+                        face.neighbors[k],
+                    ));
+                }
+            }
+        }
+        for &f in cavity.iter() {
+            self.faces[f].alive = false;
+            self.free.push(f);
+        }
+        let mut starts = HashMap::new();
+        let mut ends = HashMap::new();
+        let mut created = Vec::with_capacity(boundary.len());
+        for &(u, w, outer) in boundary.iter() {
+            let id = self.alloc(Face {
+                vertices: [u, w, idx],
+                neighbors: [GHOST, GHOST, outer],
+                alive: true,
+            });
+            let j = (0..3)
+                .find(|&j| {
+                    let v = self.faces[outer].vertices;
+                    v[(j + 1) % 3] == w && v[(j + 2) % 3] == u
+                })
+                .unwrap();
+            self.faces[outer].neighbors[j] = id;
+            starts.insert(u, id);
+            ends.insert(w, id);
+            created.push(id);
+        }
+        for &id in created.iter() {
+            let [u, w, _] = self.faces[id].vertices;
+            self.faces[id].neighbors[0] = starts[&w];
+            self.faces[id].neighbors[1] = ends[&u];
+            for &v in [u, w].iter() {
+                if v != GHOST {
+                    self.vertex_face[v] = id;
+                }
+            }
+            self.vertex_face[idx] = id;
+            if u != GHOST && w != GHOST {
+                self.last = id;
+            }
+        }
+        for (u, w) in split {
+            self.constraints.remove(&edge_key(u, w));
+            self.constraints.insert(edge_key(u, idx));
+            self.constraints.insert(edge_key(idx, w));
+        }
+    }
+    // Lawson flips until every unconstrained edge is locally Delaunay
+    fn legalize(&mut self, mut stack: Vec<(usize, usize)>) {
+        while let Some((u, w)) = stack.pop() {
+            if u == GHOST || w == GHOST || self.is_constrained(u, w) {
+                continue;
+            }
+            let (f, k) = match self.find_edge(u, w) {
+                Some(edge) => edge,
+                None => continue,
+            };
+            let g = self.faces[f].neighbors[k];
+            if self.faces[f].is_ghost() || self.faces[g].is_ghost() {
+                continue;
+            }
+            let p = self.faces[f].vertices[k];
+            let q = self.faces[g].vertices[self.faces[g].index_of_neighbor(f)];
+            let [a, b, c] = self.faces[f].vertices;
+            let (pp, pq) = (self.points[p], self.points[q]);
+            let inside = in_circle(self.points[a], self.points[b], self.points[c], pq) > 0.0;
+            if inside && crosses(pp, pq, self.points[u], self.points[w]) {
+                self.flip(f, k);
+                stack.extend_from_slice(&[(p, u), (u, q), (q, w), (w, p)]);
+            }
+        }
+    }
+    // replaces the edge opposite vertices[k] of face f by the other diagonal of the quad
+    fn flip(&mut self, f: usize, k: usize) {
+        let face = self.faces[f];
+        let a = face.vertices[k];
+        let b = face.vertices[(k + 1) % 3];
+        let c = face.vertices[(k + 2) % 3];
+        let g = face.neighbors[k];
+        let n_ca = face.neighbors[(k + 1) % 3];
+        let n_ab = face.neighbors[(k + 2) % 3];
+        let other = self.faces[g];
+        let j = other.index_of_neighbor(f);
+        let d = other.vertices[j];
+        let n_bd = other.neighbors[(j + 1) % 3];
+        let n_dc = other.neighbors[(j + 2) % 3];
+        self.faces[f].vertices = [a, b, d];
+        self.faces[f].neighbors = [n_bd, g, n_ab];
+        self.faces[g].vertices = [d, c, a];
+        self.faces[g].neighbors = [n_ca, f, n_dc];
+        self.faces[n_bd].replace_neighbor(g, f);
+        self.faces[n_ca].replace_neighbor(f, g);
+        for &(v, id) in [(a, f), (b, f), (d, f), (c, g)].iter() {
+            if v != GHOST {
+                self.vertex_face[v] = id;
+            }
+        }
+    }
+    // every face around a vertex with the vertex position inside it
+    fn faces_around(&self, vertex: usize) -> Vec<(usize, usize)> {
+        let start = self.vertex_face[vertex];
+        let mut result = Vec::new();
+        let mut face = start;
+        while let Some(k) = self.faces[face].index_of(vertex) {
+            result.push((face, k));
+            face = self.faces[face].neighbors[(k + 2) % 3];
+            if face == start || result.len() > self.faces.len() {
+                break;
+            }
+        }
+        result
+    }
+    // a face holding the edge and the index of the vertex opposite to it
+    fn find_edge(&self, u: usize, w: usize) -> Option<(usize, usize)> {
+        for (f, k) in self.faces_around(u) {
+            let vertices = self.faces[f].vertices;
+            if vertices[(k + 1) % 3] == w {
+                return Some((f, (k + 2) % 3));
+            }
+            if vertices[(k + 2) % 3] == w {
+                return Some((f, (k + 1) % 3));
+            }
+        }
+        None
+    }
+    // ghost faces count the open half plane beyond their hull edge as their circle
+    fn in_circle(&self, id: usize, point: Point) -> bool {
+        let face = &self.faces[id];
+        match face.index_of(GHOST) {
+            Some(k) => {
+                let a = self.points[face.vertices[(k + 1) % 3]];
+                let b = self.points[face.vertices[(k + 2) % 3]];
+                let o = orient(a, b, point);
+                o > 0.0 || (o == 0.0 && strictly_between(a, b, point))
+            }
+            None => {
+                let [a, b, c] = face.vertices;
+                in_circle(self.points[a], self.points[b], self.points[c], point) > 0.0
+            }
+        }
+    }
+    fn on_segment(&self, u: usize, w: usize, point: Point) -> bool {
+        let (a, b) = (self.points[u], self.points[w]);
+        let length = (b.x as f64 - a.x as f64).powi(2) + (b.y as f64 - a.y as f64).powi(2);
+        orient(a, b, point).abs() <= EPSILON * length && strictly_between(a, b, point)
+    }
+}
+
+fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+// positive if d lies inside the circumcircle of the counter-clockwise triangle abc
+fn in_circle(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let (adx, ady) = (a.x as f64 - d.x as f64, a.y as f64 - d.y as f64);
+    let (bdx, bdy) = (b.x as f64 - d.x as f64, b.y as f64 - d.y as f64);
+    let (cdx, cdy) = (c.x as f64 - d.x as f64, c.y as f64 - d.y as f64);
+    (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+        + (bdx * bdx + bdy * bdy) * (cdx * ady - adx * cdy)
+        + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady)
+}
+
+fn strictly_between(a: Point, b: Point, point: Point) -> bool {
+    let dot = |o: Point, p: Point, q: Point| {
+        (p.x as f64 - o.x as f64) * (q.x as f64 - o.x as f64)
+            + (p.y as f64 - o.y as f64) * (q.y as f64 - o.y as f64)
+    };
+    dot(a, b, point) > 0.0 && dot(b, a, point) > 0.0
+}
+
+// proper crossing of the open segments pq and uw
+fn crosses(p: Point, q: Point, u: Point, w: Point) -> bool {
+    orient(p, q, u) * orient(p, q, w) < 0.0 && orient(u, w, p) * orient(u, w, q) < 0.0
+}
+
+fn intersection(a: Point, b: Point, c: Point, d: Point) -> Point {
+    let denominator = orient(Point::zero(), b - a, d - c);
+    let t = orient(Point::zero(), c - a, d - c) / denominator;
+    Point::new(
+        (a.x as f64 + t * (b.x as f64 - a.x as f64)) as f32,
+        (a.y as f64 + t * (b.y as f64 - a.y as f64)) as f32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::delaunay::*;
+    use crate::base::{Line, Point};
+    use crate::shape::Polygon;
+
+    // adjacency is symmetric and no vertex lies inside a circumcircle unless
+    // a constraint separates them
+    fn assert_valid(triangulation: &Triangulation, delaunay: bool) {
+        for id in triangulation.triangle_ids() {
+            assert!(triangulation.triangle(id).signed_area() > 0.0);
+            for neighbor in triangulation.neighbors(id).iter().flatten() {
+                assert!(triangulation.neighbors(*neighbor).contains(&Some(id)));
+            }
+            if !delaunay {
+                continue;
+            }
+            let [a, b, c] = triangulation.vertices(id);
+            let points = triangulation.points();
+            for (idx, &point) in points.iter().enumerate() {
+                if idx == a || idx == b || idx == c {
+                    continue;
+                }
+                let value = in_circle(points[a], points[b], points[c], point);
+                assert!(value <= 1e-3, "{} inside circumcircle of {}", point, id);
+            }
+        }
+    }
+
+    fn hull_area(triangulation: &Triangulation) -> f32 {
+        triangulation
+            .triangles()
+            .iter()
+            .map(|triangle| triangle.area())
+            .sum()
+    }
+
+    fn scattered(count: usize) -> Vec<Point> {
+        let mut state = 12345u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        (0..count)
+            .map(|_| Point::new(100.0 * next(), 100.0 * next()))
+            .collect()
+    }
+
+    #[test]
+    fn test_square() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 4.0),
+            Point::new(2.0, 2.0),
+        ];
+        let triangulation = Triangulation::from_points(&points);
+        assert_eq!(triangulation.triangle_count(), 4);
+        assert_eq!(triangulation.edges().len(), 8);
+        assert_eq!(hull_area(&triangulation), 16.0);
+        assert_eq!(triangulation.vertex_neighbors(4).len(), 4);
+        assert_valid(&triangulation, true);
+    }
+    #[test]
+    fn test_scattered() {
+        let points = scattered(200);
+        let triangulation = Triangulation::from_points(&points);
+        assert_valid(&triangulation, true);
+        // Euler: 2n - 2 - h triangles for n points with h on the hull
+        let hull = Polygon::new(&points).convex_hull();
+        assert_eq!(
+            triangulation.triangle_count(),
+            2 * points.len() - 2 - hull.vertices.len()
+        );
+        assert!((hull_area(&triangulation) - hull.area()).abs() < 1e-2);
+    }
+    #[test]
+    fn test_grid() {
+        // cocircular points everywhere
+        let mut points = Vec::new();
+        for x in 0..6 {
+            for y in 0..5 {
+                points.push(Point::new(x as f32, y as f32));
+            }
+        }
+        let triangulation = Triangulation::from_points(&points);
+        assert_eq!(triangulation.triangle_count(), 2 * 5 * 4);
+        assert_eq!(hull_area(&triangulation), 20.0);
+        assert_valid(&triangulation, true);
+    }
+    #[test]
+    fn test_collinear_start_and_duplicates() {
+        let mut triangulation = Triangulation::new();
+        assert_eq!(triangulation.insert(Point::new(0.0, 0.0)), 0);
+        assert_eq!(triangulation.insert(Point::new(1.0, 0.0)), 1);
+        assert_eq!(triangulation.insert(Point::new(2.0, 0.0)), 2);
+        assert_eq!(triangulation.triangle_count(), 0);
+        assert_eq!(triangulation.insert(Point::new(1.0, 0.0)), 1);
+        assert_eq!(triangulation.insert(Point::new(1.0, 1.0)), 3);
+        assert_eq!(triangulation.triangle_count(), 2);
+        assert_eq!(triangulation.insert(Point::new(1.0, 1.0)), 3);
+        assert_valid(&triangulation, true);
+    }
+    #[test]
+    fn test_locate_and_insert_outside() {
+        let mut triangulation = Triangulation::from_points(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 4.0),
+        ]);
+        let id = triangulation.locate(Point::new(1.0, 1.0)).unwrap();
+        assert!(triangulation.triangle(id).contains(Point::new(1.0, 1.0)));
+        assert_eq!(triangulation.locate(Point::new(5.0, 5.0)), None);
+        triangulation.insert(Point::new(5.0, 5.0));
+        triangulation.insert(Point::new(-3.0, 2.0));
+        assert!(triangulation.locate(Point::new(3.0, 3.0)).is_some());
+        assert_valid(&triangulation, true);
+        assert_eq!(hull_area(&triangulation), 26.0);
+    }
+    #[test]
+    fn test_constraint() {
+        // the Delaunay diagonal of this diamond is the short vertical one
+        let mut triangulation = Triangulation::from_points(&[
+            Point::new(0.0, 0.0),
+            Point::new(5.0, -1.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 1.0),
+        ]);
+        assert!(triangulation.has_edge(1, 3));
+        assert!(triangulation.add_constraint(0, 2));
+        assert!(triangulation.has_edge(0, 2));
+        assert!(!triangulation.has_edge(1, 3));
+        assert!(triangulation.is_constrained(2, 0));
+        // a new point cannot flip the constraint away
+        triangulation.insert(Point::new(5.0, 0.5));
+        assert!(triangulation.has_edge(0, 2));
+        assert_valid(&triangulation, false);
+    }
+    #[test]
+    fn test_concave_polygon() {
+        let polygon = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(9.0, 10.0),
+            Point::new(9.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 10.0),
+            Point::new(0.0, 10.0),
+        ]);
+        let mut triangulation = Triangulation::from_polygon(&polygon);
+        for &point in scattered(50).iter() {
+            triangulation.insert(point * 0.1);
+        }
+        for i in 0..polygon.vertices.len() {
+            assert!(triangulation.is_constrained(i, (i + 1) % polygon.vertices.len()));
+        }
+        let inside: f32 = triangulation
+            .triangles()
+            .iter()
+            .filter(|triangle| polygon.is_inside(triangle.centroid()))
+            .map(|triangle| triangle.area())
+            .sum();
+        assert!((inside - polygon.area()).abs() < 1e-3);
+        assert_valid(&triangulation, false);
+    }
+    #[test]
+    fn test_crossing_constraints() {
+        let mut triangulation = Triangulation::new();
+        triangulation.insert_line(Line::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0)));
+        triangulation.insert_line(Line::new(Point::new(0.0, 10.0), Point::new(10.0, 0.0)));
+        // the crossing becomes a vertex shared by four constrained edges
+        let center = triangulation.insert(Point::new(5.0, 5.0));
+        assert_eq!(triangulation.points().len(), 5);
+        assert_eq!(triangulation.constraints().count(), 4);
+        for corner in 0..4 {
+            assert!(triangulation.is_constrained(corner, center));
+        }
+        assert_valid(&triangulation, false);
+    }
+    #[test]
+    fn test_collinear_constraint() {
+        let mut triangulation = Triangulation::from_points(&[
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(3.0, 5.0),
+            Point::new(3.0, -5.0),
+        ]);
+        triangulation.add_constraint(0, 2);
+        assert!(triangulation.is_constrained(0, 1));
+        assert!(triangulation.is_constrained(1, 2));
+        assert!(!triangulation.is_constrained(0, 2));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::base::Point;
+use crate::shape::Circle;
+
+pub fn minimum_enclosing_circle(points: &[Point]) -> Circle {
+    if points.is_empty() {
+        return Circle::default();
+    }
+    // Welzl's algorithm in its iterative form, expected linear time on shuffled input
+    let mut points = points.to_vec();
+    shuffle(&mut points);
+    let mut disk = Disk::from_point(points[0]);
+    for i in 1..points.len() {
+        if disk.contains(points[i]) {
+            continue;
+        }
+        disk = Disk::from_point(points[i]);
+        for j in 0..i {
+            if disk.contains(points[j]) {
+                continue;
+            }
+            disk = Disk::from_two(points[i], points[j]);
+            for k in 0..j {
+                if !disk.contains(points[k]) {
+                    disk = Disk::from_three(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+    Circle::new(Point::new(disk.x as f32, disk.y as f32), disk.radius as f32)
+}
+
+// deterministic xorshift, the input order only has to be decorrelated
+fn shuffle(points: &mut [Point]) {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64 ^ points.len() as u64;
+    for i in (1..points.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        points.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Disk {
+    x: f64,
+    y: f64,
+    radius: f64,
+}
+
+impl Disk {
+    fn from_point(point: Point) -> Self {
+        Self {
+            x: point.x as f64,
+            y: point.y as f64,
+            radius: 0.0,
+        }
+    }
+    fn from_two(a: Point, b: Point) -> Self {
+        let (ax, ay, bx, by) = (a.x as f64, a.y as f64, b.x as f64, b.y as f64);
+        let x = (ax + bx) / 2.0;
+        let y = (ay + by) / 2.0;
+        Self {
+            x,
+            y,
+            radius: ((ax - x).powi(2) + (ay - y).powi(2)).sqrt(),
+        }
+    }
+    fn from_three(a: Point, b: Point, c: Point) -> Self {
+        let (ax, ay) = (a.x as f64, a.y as f64);
+        let (bx, by) = (b.x as f64 - ax, b.y as f64 - ay);
+        let (cx, cy) = (c.x as f64 - ax, c.y as f64 - ay);
+        let d = 2.0 * (bx * cy - by * cx);
+        if d.abs() < 1e-12 {
+            // collinear, the outermost pair spans the circle
+            let candidates = [
+                Self::from_two(a, b),
+                Self::from_two(a, c),
+                Self::from_two(b, c),
+            ];
+            return candidates
+                .iter()
+                .copied()
+                .fold(candidates[0], |best, disk| {
+                    if disk.radius > best.radius {
+                        disk
+                    } else {
+                        best
+                    }
+                });
+        }
+        let b2 = bx * bx + by * by;
+        let c2 = cx * cx + cy * cy;
+        let ux = (cy * b2 - by * c2) / d;
+        let uy = (bx * c2 - cx * b2) / d;
+        Self {
+            x: ax + ux,
+            y: ay + uy,
+            radius: (ux * ux + uy * uy).sqrt(),
+        }
+    }
+    fn contains(&self, point: Point) -> bool {
+        let distance =
+            ((point.x as f64 - self.x).powi(2) + (point.y as f64 - self.y).powi(2)).sqrt();
+        distance <= self.radius * (1.0 + 1e-6) + 1e-6
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::enclosing::minimum_enclosing_circle;
+    use crate::base::Point;
+    use crate::shape::{Circle, Shape};
+
+    #[test]
+    fn test_two_points() {
+        let circle = minimum_enclosing_circle(&[Point::new(-1.0, 2.0), Point::new(3.0, 2.0)]);
+        assert_eq!(circle.center(), Point::new(1.0, 2.0));
+        assert_eq!(circle.radius(), 2.0);
+    }
+    #[test]
+    fn test_triangle() {
+        // obtuse triangle is spanned by its longest side
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 1.0),
+        ];
+        let circle = minimum_enclosing_circle(&points);
+        assert_eq!(circle.center(), Point::new(5.0, 0.0));
+        assert_eq!(circle.radius(), 5.0);
+        // acute triangle uses the circumcircle
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 3.0),
+        ];
+        let circle = minimum_enclosing_circle(&points);
+        let expected = Point::new(2.0, 5.0 / 6.0);
+        assert_eq!(circle.center(), expected);
+        assert!((circle.radius() - expected.distance_to(Point::zero())).abs() < 1e-5);
+    }
+    #[test]
+    fn test_point_cloud() {
+        let mut points = Vec::new();
+        for i in 0..200 {
+            let t = i as f32 * 0.37;
+            points.push(Point::new(
+                3.0 + 4.0 * t.cos() * (t * 0.11).sin(),
+                -2.0 + 4.0 * t.sin(),
+            ));
+        }
+        let circle = minimum_enclosing_circle(&points);
+        let mut on_boundary = 0;
+        for &point in points.iter() {
+            let distance = point.distance_to(circle.center());
+            assert!(distance <= circle.radius() + 1e-4);
+            if distance > circle.radius() - 1e-4 {
+                on_boundary += 1;
+            }
+        }
+        // a minimal circle is supported by at least two points
+        assert!(on_boundary >= 2);
+        assert!(circle.radius() <= 4.0 + 1e-4);
+    }
+    #[test]
+    fn test_empty() {
+        assert_eq!(minimum_enclosing_circle(&[]), Circle::default());
+    }
+}
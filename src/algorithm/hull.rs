@@ -0,0 +1,295 @@
+use crate::base::{Point, Vector};
+use crate::shape::{Polygon, Shape};
+
+pub const DEFAULT_TOLERANCE: f32 = 1e-5;
+
+pub fn convex_hull(points: &[Point]) -> Polygon {
+    convex_hull_with_tolerance(points, DEFAULT_TOLERANCE)
+}
+
+// Monotone chain. Points closer than `tolerance` to a hull edge count as collinear
+// and are dropped, so the result is a counter-clockwise polygon of strict corners.
+pub fn convex_hull_with_tolerance(points: &[Point], tolerance: f32) -> Polygon {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap()
+            .then(a.y.partial_cmp(&b.y).unwrap())
+    });
+    if sorted.len() < 3 {
+        sorted.dedup();
+        return Polygon { vertices: sorted };
+    }
+    let mut lower: Vec<Point> = Vec::new();
+    for &point in sorted.iter() {
+        while lower.len() >= 2
+            && !is_corner(
+                lower[lower.len() - 2],
+                lower[lower.len() - 1],
+                point,
+                tolerance,
+            )
+        {
+            lower.pop();
+        }
+        lower.push(point);
+    }
+    let mut upper: Vec<Point> = Vec::new();
+    for &point in sorted.iter().rev() {
+        while upper.len() >= 2
+            && !is_corner(
+                upper[upper.len() - 2],
+                upper[upper.len() - 1],
+                point,
+                tolerance,
+            )
+        {
+            upper.pop();
+        }
+        upper.push(point);
+    }
+    lower.pop();
+    upper.pop();
+    lower.append(&mut upper);
+    lower.dedup();
+    if lower.len() == 2 && lower[0] == lower[1] {
+        lower.pop();
+    }
+    Polygon { vertices: lower }
+}
+
+pub fn convex_hull_of_shapes(shapes: &[&dyn Shape]) -> Polygon {
+    let mut points = Vec::new();
+    for shape in shapes.iter() {
+        points.extend_from_slice(shape.vertices());
+    }
+    convex_hull(&points)
+}
+
+// true if `corner` lies more than `tolerance` to the right of the chord `from` -> `to`,
+// which makes it a strict left turn of the chain
+fn is_corner(from: Point, corner: Point, to: Point, tolerance: f32) -> bool {
+    let chord = Vector::from_points(from, to);
+    let offset = Vector::from_points(from, corner);
+    let length = chord.magnitude();
+    if length == 0.0 {
+        return offset.magnitude() > tolerance;
+    }
+    chord.cross(offset) / length < -tolerance
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IncrementalHull {
+    tolerance: f32,
+    vertices: Vec<Point>,
+}
+
+#[allow(dead_code)]
+impl IncrementalHull {
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            tolerance,
+            vertices: Vec::new(),
+        }
+    }
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+    pub fn polygon(&self) -> Polygon {
+        Polygon::new(&self.vertices)
+    }
+    pub fn contains(&self, point: Point) -> bool {
+        if self.vertices.len() < 3 {
+            return self.vertices.contains(&point);
+        }
+        self.visible_edges(point).is_none()
+    }
+    // returns true if the hull changed
+    pub fn insert(&mut self, point: Point) -> bool {
+        if self.vertices.len() < 3 {
+            // still degenerate, rebuild from the few points we have
+            let mut points = self.vertices.clone();
+            points.push(point);
+            let hull = convex_hull_with_tolerance(&points, self.tolerance).vertices;
+            let changed = hull != self.vertices;
+            self.vertices = hull;
+            return changed;
+        }
+        let (first, last) = match self.visible_edges(point) {
+            Some(range) => range,
+            None => return false,
+        };
+        // keep the chain from the end of the last visible edge to the start of the first
+        let n = self.vertices.len();
+        let mut vertices = Vec::with_capacity(n + 1);
+        let mut idx = (last + 1) % n;
+        loop {
+            vertices.push(self.vertices[idx]);
+            if idx == first {
+                break;
+            }
+            idx = (idx + 1) % n;
+        }
+        vertices.push(point);
+        // neighbours the new point left in line with it are no corners any more
+        while vertices.len() > 3 {
+            let k = vertices.len();
+            if is_corner(vertices[k - 3], vertices[k - 2], point, self.tolerance) {
+                break;
+            }
+            vertices.remove(k - 2);
+        }
+        while vertices.len() > 3 && !is_corner(point, vertices[0], vertices[1], self.tolerance) {
+            vertices.remove(0);
+        }
+        self.vertices = vertices;
+        true
+    }
+    pub fn extend(&mut self, points: &[Point]) {
+        for &point in points.iter() {
+            self.insert(point);
+        }
+    }
+    // edges i -> i + 1 that see the point from outside form a contiguous range
+    fn visible_edges(&self, point: Point) -> Option<(usize, usize)> {
+        let n = self.vertices.len();
+        let visible: Vec<bool> = (0..n)
+            .map(|i| {
+                is_corner(
+                    self.vertices[i],
+                    point,
+                    self.vertices[(i + 1) % n],
+                    self.tolerance,
+                )
+            })
+            .collect();
+        let first = (0..n).find(|&i| visible[i] && !visible[(i + n - 1) % n])?;
+        let mut last = first;
+        while visible[(last + 1) % n] && (last + 1) % n != first {
+            last = (last + 1) % n;
+        }
+        Some((first, last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::hull::*;
+    use crate::base::{Angle, Point, Size};
+    use crate::shape::{Circle, Polygon, Rectangle, Shape};
+
+    fn square_with_noise() -> Vec<Point> {
+        vec![
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 0.0), // collinear on the bottom edge
+            Point::new(2.0, 2.0),
+            Point::new(0.5, 1.5),
+            Point::new(0.0, 2.0),
+            Point::new(0.0, 2.0), // duplicate
+            Point::new(2.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let hull = convex_hull(&square_with_noise());
+        let expected = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        assert_eq!(hull.vertices, expected);
+        assert!(hull.signed_area() > 0.0, "hull is counter-clockwise");
+    }
+    #[test]
+    fn test_tolerance() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, -0.01),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 5.0),
+        ];
+        assert_eq!(convex_hull(&points).vertices.len(), 4);
+        assert_eq!(convex_hull_with_tolerance(&points, 0.1).vertices.len(), 3);
+    }
+    #[test]
+    fn test_degenerate() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+        ];
+        let hull = convex_hull(&points);
+        assert_eq!(
+            hull.vertices,
+            vec![Point::new(0.0, 0.0), Point::new(2.0, 2.0)]
+        );
+        let hull = convex_hull(&[Point::new(1.0, 1.0), Point::new(1.0, 1.0)]);
+        assert_eq!(hull.vertices, vec![Point::new(1.0, 1.0)]);
+    }
+    #[test]
+    fn test_incremental() {
+        let points = square_with_noise();
+        let mut hull = IncrementalHull::new(DEFAULT_TOLERANCE);
+        hull.extend(&points);
+        let batch = convex_hull(&points);
+        assert_eq!(hull.vertices().len(), batch.vertices.len());
+        for vertex in batch.vertices.iter() {
+            assert!(hull.vertices().contains(vertex));
+        }
+        assert!(hull.polygon().signed_area() > 0.0);
+        assert!(!hull.insert(Point::new(1.0, 1.0)));
+        assert!(hull.insert(Point::new(3.0, 1.0)));
+        assert_eq!(hull.vertices().len(), 5);
+        assert!(hull.insert(Point::new(10.0, 1.0)));
+        // the far point hides the previous right corner
+        assert_eq!(hull.vertices().len(), 5);
+        assert!(hull.vertices().contains(&Point::new(10.0, 1.0)));
+        assert!(!hull.vertices().contains(&Point::new(3.0, 1.0)));
+    }
+    #[test]
+    fn test_incremental_collinear() {
+        let mut hull = IncrementalHull::new(DEFAULT_TOLERANCE);
+        hull.extend(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 4.0),
+        ]);
+        assert!(!hull.insert(Point::new(2.0, 0.0)));
+        assert_eq!(hull.vertices().len(), 4);
+        // growing along an edge drops the corner it passes
+        let mut hull = IncrementalHull::new(DEFAULT_TOLERANCE);
+        hull.extend(&[
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ]);
+        assert!(hull.insert(Point::new(4.0, 0.0)));
+        assert!(hull.insert(Point::new(4.0, 2.0)));
+        assert_eq!(hull.vertices().len(), 4);
+        assert!(!hull.vertices().contains(&Point::new(2.0, 0.0)));
+        assert!(!hull.vertices().contains(&Point::new(2.0, 2.0)));
+        assert_eq!(hull.polygon().area(), 8.0);
+    }
+    #[test]
+    fn test_convex_hull_of_shapes() {
+        let circle = Circle::new(Point::new(0.0, 0.0), 1.0);
+        let rect = Rectangle::new(Point::new(5.0, 0.0), Size::new(2.0, 2.0), Angle::zero());
+        let triangle = Polygon::new(&[
+            Point::new(2.0, 4.0),
+            Point::new(3.0, 4.0),
+            Point::new(2.5, 5.0),
+        ]);
+        let hull = convex_hull_of_shapes(&[&circle, &rect, &triangle]);
+        assert!(hull.signed_area() > 0.0);
+        let bounding_box = hull.bounding_box();
+        assert_eq!(bounding_box.x1(), -1.0);
+        assert_eq!(bounding_box.x2(), 6.0);
+        assert_eq!(bounding_box.y2(), 5.0);
+    }
+}
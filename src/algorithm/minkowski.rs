@@ -0,0 +1,196 @@
+use crate::algorithm::hull::convex_hull;
+use crate::algorithm::offset::{offset_ring, JoinStyle};
+use crate::base::{Point, Vector};
+use crate::shape::{Circle, PointLocation, Polygon, Shape};
+
+// Inputs are treated as convex, anything else is replaced by its convex hull. The
+// result is counter-clockwise.
+pub fn minkowski_sum(a: &[Point], b: &[Point]) -> Polygon {
+    let (p, q) = (lowest_first(a), lowest_first(b));
+    let (n, m) = (p.len(), q.len());
+    if n == 0 || m == 0 {
+        return Polygon::default();
+    }
+    if n < 3 || m < 3 {
+        let sums: Vec<Point> = p
+            .iter()
+            .flat_map(|&u| q.iter().map(move |&v| u + v))
+            .collect();
+        return convex_hull(&sums);
+    }
+    // both start at their lowest vertex, merge the edges by direction
+    let mut vertices = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        vertices.push(p[i % n] + q[j % m]);
+        let turn = if i == n {
+            -1.0
+        } else if j == m {
+            1.0
+        } else {
+            let u = Vector::from_points(p[i], p[(i + 1) % n]);
+            let v = Vector::from_points(q[j], q[(j + 1) % m]);
+            u.cross(v)
+        };
+        if turn >= 0.0 {
+            i += 1;
+        }
+        if turn <= 0.0 {
+            j += 1;
+        }
+    }
+    Polygon { vertices }
+}
+
+// Every difference of a point in `a` and one in `b`. Contains the origin exactly when
+// the shapes overlap.
+pub fn minkowski_difference(a: &[Point], b: &[Point]) -> Polygon {
+    let negated: Vec<Point> = b.iter().map(|&point| Point::zero() - point).collect();
+    minkowski_sum(a, &negated)
+}
+
+// The convex polygon grown by the circle radius with rounded corners, and moved by
+// the circle centre.
+pub fn minkowski_sum_circle(vertices: &[Point], circle: &Circle) -> Polygon {
+    let hull = convex_hull(vertices);
+    let radius = circle.radius();
+    let mut result = match hull.vertices.len() {
+        0 => return Polygon::default(),
+        1 => Circle::with_tessellation(hull.vertices[0], radius, circle.tessellation()).polygon(),
+        _ if radius <= 0.0 => hull,
+        _ => {
+            // a convex ring grown outwards stays simple, so nothing needs resolving,
+            // and a segment is offset as a ring walking it forward and back
+            Polygon {
+                vertices: offset_ring(
+                    &hull.vertices,
+                    radius,
+                    JoinStyle::Round,
+                    circle.tessellation(),
+                ),
+            }
+        }
+    };
+    result.translate(circle.center().to_vector());
+    result
+}
+
+// Shortest translation that moves `a` out of `b`, matching `Hit::delta`. None if the
+// shapes do not overlap or only touch.
+pub fn penetration(a: &[Point], b: &[Point]) -> Option<Vector> {
+    let difference = minkowski_difference(a, b);
+    if difference.vertices.len() < 3 || difference.locate(Point::zero()) != PointLocation::Inside {
+        return None;
+    }
+    let exit = difference.closest_point(Point::zero());
+    Some(Vector::from_points(exit, Point::zero()))
+}
+
+fn lowest_first(vertices: &[Point]) -> Vec<Point> {
+    let mut hull = convex_hull(vertices).vertices;
+    let lowest = (0..hull.len()).min_by(|&i, &j| {
+        let (a, b) = (hull[i], hull[j]);
+        a.y.partial_cmp(&b.y)
+            .unwrap()
+            .then(a.x.partial_cmp(&b.x).unwrap())
+    });
+    if let Some(lowest) = lowest {
+        hull.rotate_left(lowest);
+    }
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::minkowski::*;
+    use crate::base::{Angle, Point, Vector};
+    use crate::collision::{BoundingBox, Collidable};
+    use crate::shape::{Circle, Polygon, Tessellation};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_sum() {
+        let triangle = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let sum = minkowski_sum(
+            &BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners(),
+            &triangle,
+        );
+        // the square plus a triangle, with a bevelled corner
+        assert_eq!(sum.vertices.len(), 5);
+        assert_eq!(sum.area(), 9.0 - 0.5);
+        assert!(sum.signed_area() > 0.0);
+        // either winding, the result only depends on the point sets
+        let mut reversed = triangle.clone();
+        reversed.reverse();
+        assert_eq!(
+            minkowski_sum(
+                &reversed,
+                &BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners()
+            )
+            .area(),
+            8.5
+        );
+        let sum = minkowski_sum(
+            &BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners(),
+            &[Point::new(3.0, 1.0)],
+        );
+        assert_eq!(
+            Polygon::new(&BoundingBox::from_edges(3.0, 1.0, 5.0, 3.0).corners()).area(),
+            sum.area()
+        );
+        assert!(sum.vertices.contains(&Point::new(5.0, 3.0)));
+    }
+    #[test]
+    fn test_difference() {
+        let a = BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners();
+        let b = BoundingBox::from_edges(1.5, 0.5, 3.5, 2.5).corners();
+        let difference = minkowski_difference(&a, &b);
+        assert_eq!(difference.area(), 16.0);
+        assert!(difference.is_inside(Point::zero()));
+        assert!(
+            !minkowski_difference(&a, &BoundingBox::from_edges(3.0, 0.0, 4.0, 1.0).corners())
+                .is_inside(Point::zero())
+        );
+    }
+    #[test]
+    fn test_sum_circle() {
+        let circle = Circle::new(Point::new(10.0, 0.0), 1.0);
+        let sum = minkowski_sum_circle(
+            &BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners(),
+            &circle,
+        );
+        // the corners are rounded as finely as the circle itself
+        assert!((sum.area() - (4.0 + 8.0 + circle.polygon().area())).abs() < 1e-4);
+        let fine = Tessellation::new(1e-3, 8, 512, Angle::zero());
+        let smooth = Circle::with_tessellation(circle.center(), 1.0, fine);
+        let sum = minkowski_sum_circle(
+            &BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners(),
+            &smooth,
+        );
+        assert!(sum.vertices.len() > 4 * 4);
+        assert!((sum.area() - (4.0 + 8.0 + PI)).abs() < 5e-3);
+        assert!(sum.is_inside(Point::new(9.2, 1.0)));
+        assert!(!sum.is_inside(Point::new(13.2, 1.0)));
+        let point = minkowski_sum_circle(&[Point::new(1.0, 1.0)], &circle);
+        assert!(point.is_inside(Point::new(11.0, 1.5)));
+    }
+    #[test]
+    fn test_penetration() {
+        let a = BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0);
+        let b = BoundingBox::from_edges(1.5, 0.5, 3.5, 2.5);
+        let delta = penetration(&a.corners(), &b.corners()).unwrap();
+        assert_eq!(delta, Vector::new(-0.5, 0.0));
+        assert_eq!(delta, a.hit_bounding_box(b).unwrap().delta);
+        assert_eq!(
+            penetration(
+                &a.corners(),
+                &BoundingBox::from_edges(2.0, 0.0, 3.0, 1.0).corners()
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,15 @@
+pub mod boolean;
+pub mod calipers;
+pub mod decomposition;
+pub mod delaunay;
+pub mod enclosing;
+pub mod hull;
+pub mod minkowski;
+pub mod offset;
+pub mod raster;
+pub mod sampling;
+pub mod sdf;
+pub mod simplify;
+pub mod triangulation;
+pub mod validation;
+pub mod voronoi;
@@ -0,0 +1,289 @@
+use std::f32::consts::PI;
+
+use crate::algorithm::boolean::{resolve, FillRule};
+use crate::base::{Point, Vector};
+use crate::shape::{MultiPolygon, Polygon, Tessellation};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinStyle {
+    // the limit is the longest allowed miter as a multiple of the distance,
+    // sharper corners fall back to a bevel
+    Miter(f32),
+    Round,
+    Bevel,
+}
+
+// Rings must wind counter-clockwise around filled area and clockwise around holes.
+// Each ring is offset on its own and the overlapping raw outlines are resolved with
+// the positive fill rule, which drops the loops left by collapsed parts. Round joins
+// are split into arcs as fine as the tessellation asks for. None if resolving fails,
+// see `boolean::resolve`.
+pub fn offset_rings(
+    rings: &[&[Point]],
+    distance: f32,
+    join: JoinStyle,
+    tessellation: Tessellation,
+) -> Option<MultiPolygon> {
+    let raw: Vec<Polygon> = rings
+        .iter()
+        .map(|ring| Polygon {
+            vertices: offset_ring(ring, distance, join, tessellation),
+        })
+        .filter(|ring| ring.vertices.len() >= 3)
+        .collect();
+    resolve(&raw, FillRule::Positive)
+}
+
+// Outline of everything within `distance` of the path. Round joins also round the
+// ends, the other styles cut them off square at the end points.
+pub fn offset_polyline(
+    vertices: &[Point],
+    distance: f32,
+    join: JoinStyle,
+    tessellation: Tessellation,
+) -> Option<MultiPolygon> {
+    let mut ring = dedup(vertices);
+    if ring.len() < 2 {
+        return Some(MultiPolygon::default());
+    }
+    // walk the path forward and back again to get a ring of zero width around it
+    let back: Vec<Point> = ring[1..ring.len() - 1].iter().rev().copied().collect();
+    ring.extend(back);
+    offset_rings(&[&ring], distance.abs(), join, tessellation)
+}
+
+fn dedup(vertices: &[Point]) -> Vec<Point> {
+    let mut result: Vec<Point> = Vec::with_capacity(vertices.len());
+    for &vertex in vertices.iter() {
+        if result.last() != Some(&vertex) {
+            result.push(vertex);
+        }
+    }
+    while result.len() > 1 && result.first() == result.last() {
+        result.pop();
+    }
+    result
+}
+
+// A single raw outline, simple only for convex rings grown outwards.
+pub(crate) fn offset_ring(
+    vertices: &[Point],
+    distance: f32,
+    join: JoinStyle,
+    tessellation: Tessellation,
+) -> Vec<Point> {
+    let ring = dedup(vertices);
+    let n = ring.len();
+    if n < 2 {
+        return Vec::new();
+    }
+    let mut result = Vec::with_capacity(2 * n);
+    for i in 0..n {
+        let point = ring[i];
+        let d0 = Vector::from_points(ring[(i + n - 1) % n], point).get_unit_vector();
+        let d1 = Vector::from_points(point, ring[(i + 1) % n]).get_unit_vector();
+        // the outward normal of a counter-clockwise ring is on the right
+        let n0 = -d0.get_normal_vector();
+        let n1 = -d1.get_normal_vector();
+        let cross = d0.cross(d1);
+        let dot = d0.dot(d1);
+        let straight = cross.abs() < 1e-6;
+        if straight && dot > 0.0 {
+            result.push(point + n0 * distance);
+            continue;
+        }
+        let reversal = straight && dot < 0.0;
+        if !(cross * distance > 0.0 || (reversal && distance > 0.0)) {
+            // the offset edges overlap here, route through the vertex and let the
+            // fill rule cut the loop away
+            result.push(point + n0 * distance);
+            result.push(point);
+            result.push(point + n1 * distance);
+            continue;
+        }
+        match join {
+            JoinStyle::Bevel => {
+                result.push(point + n0 * distance);
+                result.push(point + n1 * distance);
+            }
+            JoinStyle::Miter(limit) => {
+                if !reversal && (2.0 / (1.0 + dot)).sqrt() <= limit {
+                    result.push(point + (n0 + n1) * (distance / (1.0 + dot)));
+                } else {
+                    result.push(point + n0 * distance);
+                    result.push(point + n1 * distance);
+                }
+            }
+            JoinStyle::Round => {
+                let theta = if reversal { PI } else { cross.atan2(dot) };
+                let steps = tessellation.segments(distance.abs(), theta);
+                for step in 0..=steps {
+                    let mut normal = n0;
+                    normal.rotate(theta * step as f32 / steps as f32);
+                    result.push(point + normal * distance);
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::offset::*;
+    use crate::base::{Angle, Point};
+    use crate::collision::BoundingBox;
+    use std::f32::consts::PI;
+
+    fn assert_close(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() < tolerance, "{} == {}", a, b);
+    }
+
+    // fine enough for the rounded areas to come out close to the exact ones
+    fn fine() -> Tessellation {
+        Tessellation::new(1e-3, 8, 512, Angle::zero())
+    }
+
+    #[test]
+    fn test_joins() {
+        let ring = BoundingBox::from_edges(0.0, 0.0, 4.0, 4.0).corners();
+        let result = offset_rings(
+            &[&ring],
+            1.0,
+            JoinStyle::Miter(2.0),
+            Tessellation::default(),
+        )
+        .unwrap();
+        assert_eq!(result.polygons[0].outer.vertices.len(), 4);
+        assert_close(result.area(), 36.0, 1e-3);
+        let result =
+            offset_rings(&[&ring], 1.0, JoinStyle::Bevel, Tessellation::default()).unwrap();
+        assert_eq!(result.polygons[0].outer.vertices.len(), 8);
+        assert_close(result.area(), 36.0 - 2.0, 1e-3);
+        let result = offset_rings(&[&ring], 1.0, JoinStyle::Round, fine()).unwrap();
+        assert_close(result.area(), 32.0 + PI, 5e-2);
+        // a square corner needs a miter of sqrt(2) times the distance
+        let result = offset_rings(
+            &[&ring],
+            1.0,
+            JoinStyle::Miter(1.2),
+            Tessellation::default(),
+        )
+        .unwrap();
+        assert_close(result.area(), 34.0, 1e-3);
+    }
+    #[test]
+    fn test_shrink() {
+        let ring = BoundingBox::from_edges(0.0, 0.0, 4.0, 4.0).corners();
+        for &join in [JoinStyle::Miter(2.0), JoinStyle::Round, JoinStyle::Bevel].iter() {
+            let result = offset_rings(&[&ring], -1.0, join, Tessellation::default()).unwrap();
+            assert_close(result.area(), 4.0, 1e-3);
+        }
+        assert!(offset_rings(
+            &[&ring],
+            -2.5,
+            JoinStyle::Miter(2.0),
+            Tessellation::default()
+        )
+        .unwrap()
+        .is_empty());
+    }
+    #[test]
+    fn test_shrink_splits() {
+        // dumbbell: two squares joined by a thin bridge
+        let ring = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.5),
+            Point::new(6.0, 1.5),
+            Point::new(6.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 4.0),
+            Point::new(6.0, 4.0),
+            Point::new(6.0, 2.5),
+            Point::new(4.0, 2.5),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 4.0),
+        ];
+        let result = offset_rings(
+            &[&ring],
+            -1.0,
+            JoinStyle::Miter(2.0),
+            Tessellation::default(),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_close(result.area(), 8.0, 1e-3);
+    }
+    #[test]
+    fn test_concave_grow() {
+        // the notch of a U closes when grown far enough
+        let ring = vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.0),
+            Point::new(5.0, 5.0),
+            Point::new(3.0, 5.0),
+            Point::new(3.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 5.0),
+            Point::new(0.0, 5.0),
+        ];
+        let result = offset_rings(
+            &[&ring],
+            1.0,
+            JoinStyle::Miter(2.0),
+            Tessellation::default(),
+        )
+        .unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result.polygons[0].holes.is_empty());
+        assert_close(result.area(), 49.0, 1e-3);
+    }
+    #[test]
+    fn test_hole() {
+        let outer = BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners();
+        let mut hole = BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0)
+            .corners()
+            .to_vec();
+        hole.reverse();
+        let result = offset_rings(
+            &[&outer, &hole],
+            0.5,
+            JoinStyle::Miter(2.0),
+            Tessellation::default(),
+        )
+        .unwrap();
+        assert_eq!(result.polygons[0].holes.len(), 1);
+        assert_close(result.area(), 121.0 - 1.0, 1e-3);
+        // growing by more than half the hole fills it
+        let result = offset_rings(
+            &[&outer, &hole],
+            1.5,
+            JoinStyle::Miter(2.0),
+            Tessellation::default(),
+        )
+        .unwrap();
+        assert!(result.polygons[0].holes.is_empty());
+    }
+    #[test]
+    fn test_polyline() {
+        let path = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+        ];
+        let result =
+            offset_polyline(&path, 1.0, JoinStyle::Bevel, Tessellation::default()).unwrap();
+        assert_eq!(result.len(), 1);
+        // two 2 x 4 strips overlapping in a unit square, plus the bevel triangle
+        assert_close(result.area(), 16.0 - 1.0 + 0.5, 1e-3);
+        let result = offset_polyline(&path, 1.0, JoinStyle::Round, fine()).unwrap();
+        // the round ends add a full circle, the outer corner a quarter
+        assert_close(result.area(), 15.0 + PI + PI / 4.0, 5e-2);
+        assert!(
+            offset_polyline(&path[..1], 1.0, JoinStyle::Round, Tessellation::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use crate::base::{Line, Point, Size};
+use crate::collision::BoundingBox;
+use crate::shape::{signed_distance_to_outline, Edges, Polygon, Shape};
+
+// Square cells laid over `bounds`, row 0 along the bottom edge and column 0 along the
+// left. The last row and column reach past the bounds when the size does not divide
+// them evenly. Cells are stored row after row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    bounds: BoundingBox,
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(bounds: BoundingBox, cell_size: f32, fill: T) -> Self {
+        assert!(cell_size > 0.0, "Expected a positive cell size");
+        let columns = (bounds.width() / cell_size).ceil().max(1.0) as usize;
+        let rows = (bounds.height() / cell_size).ceil().max(1.0) as usize;
+        Self {
+            bounds,
+            cell_size,
+            columns,
+            rows,
+            cells: vec![fill; columns * rows],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+    pub fn get(&self, column: usize, row: usize) -> Option<&T> {
+        if column < self.columns && row < self.rows {
+            Some(&self.cells[row * self.columns + column])
+        } else {
+            None
+        }
+    }
+    pub fn get_mut(&mut self, column: usize, row: usize) -> Option<&mut T> {
+        if column < self.columns && row < self.rows {
+            Some(&mut self.cells[row * self.columns + column])
+        } else {
+            None
+        }
+    }
+    // None outside the grid
+    pub fn cell_of(&self, point: Point) -> Option<(usize, usize)> {
+        let (column, row) = self.unclamped_cell(point);
+        if column < 0 || row < 0 || column >= self.columns as i64 || row >= self.rows as i64 {
+            return None;
+        }
+        Some((column as usize, row as usize))
+    }
+    pub fn cell_bounds(&self, column: usize, row: usize) -> BoundingBox {
+        let half = self.cell_size / 2.0;
+        BoundingBox::new(self.cell_center(column, row), Size::new(half, half))
+    }
+    pub fn cell_center(&self, column: usize, row: usize) -> Point {
+        Point::new(
+            self.bounds.x1() + (column as f32 + 0.5) * self.cell_size,
+            self.bounds.y1() + (row as f32 + 0.5) * self.cell_size,
+        )
+    }
+    fn unclamped_cell(&self, point: Point) -> (i64, i64) {
+        (
+            ((point.x - self.bounds.x1()) / self.cell_size).floor() as i64,
+            ((point.y - self.bounds.y1()) / self.cell_size).floor() as i64,
+        )
+    }
+    // the columns and rows the box overlaps, empty ranges when it misses the grid
+    fn cell_range(&self, bounds: BoundingBox) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let (x1, y1) = self.unclamped_cell(Point::new(bounds.x1(), bounds.y1()));
+        let (x2, y2) = self.unclamped_cell(Point::new(bounds.x2(), bounds.y2()));
+        let clamp = |value: i64, count: usize| value.clamp(0, count as i64) as usize;
+        (
+            clamp(x1, self.columns)..clamp(x2 + 1, self.columns),
+            clamp(y1, self.rows)..clamp(y2 + 1, self.rows),
+        )
+    }
+}
+
+// Marks the cells whose centre lies inside the outline, cells that are already set stay
+// set. Curved shapes are drawn as their tessellation.
+pub fn occupancy(shape: &dyn Shape, grid: &mut Grid<bool>) {
+    let vertices = shape.vertices();
+    let (columns, rows) = grid.cell_range(shape.bounding_box());
+    for row in rows {
+        let y = grid.cell_center(0, row).y;
+        // even-odd rule along the row, as in `Polygon::locate`
+        let mut crossings: Vec<f32> = Edges::new(vertices)
+            .filter(|side| (side.origin.y > y) != (side.end.y > y))
+            .map(|side| {
+                let (a, b) = (side.origin, side.end);
+                a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x)
+            })
+            .collect();
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for pair in crossings.chunks_exact(2) {
+            for column in columns.clone() {
+                let x = grid.cell_center(column, row).x;
+                if x >= pair[0] && x <= pair[1] {
+                    grid.cells[row * grid.columns + column] = true;
+                }
+            }
+        }
+    }
+}
+
+// The fraction of each cell the outline covers, from 0 to 1. Cells keep the larger of
+// their old and new value. Curved shapes are covered as their tessellation.
+pub fn coverage(shape: &dyn Shape, grid: &mut Grid<f32>) {
+    let vertices = shape.vertices();
+    let reach = grid.cell_size * std::f32::consts::SQRT_2 / 2.0;
+    let cell_area = grid.cell_size * grid.cell_size;
+    let (columns, rows) = grid.cell_range(shape.bounding_box());
+    for row in rows {
+        for column in columns.clone() {
+            // only cells the outline passes through need clipping
+            let distance = signed_distance_to_outline(vertices, grid.cell_center(column, row));
+            let fraction = if distance >= reach {
+                0.0
+            } else if distance <= -reach {
+                1.0
+            } else {
+                let clipped = Polygon {
+                    vertices: clip_to_box(vertices, grid.cell_bounds(column, row)),
+                };
+                (clipped.area() / cell_area).min(1.0)
+            };
+            let cell = &mut grid.cells[row * grid.columns + column];
+            *cell = cell.max(fraction);
+        }
+    }
+}
+
+// Every cell the segment touches, in order from its origin. Where it passes exactly
+// through a corner both cells beside the corner are included. Cells outside the grid
+// are left out.
+pub fn supercover<T>(line: Line, grid: &Grid<T>) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    let mut push = |column: i64, row: i64| {
+        if column >= 0 && row >= 0 && column < grid.columns as i64 && row < grid.rows as i64 {
+            cells.push((column as usize, row as usize));
+        }
+    };
+    let (mut column, mut row) = grid.unclamped_cell(line.origin);
+    let (last_column, last_row) = grid.unclamped_cell(line.end);
+    let (dx, dy) = (line.end.x - line.origin.x, line.end.y - line.origin.y);
+    let (step_x, step_y) = (sign(dx), sign(dy));
+    // the part of the segment it takes to cross one cell
+    let (delta_x, delta_y) = (grid.cell_size / dx.abs(), grid.cell_size / dy.abs());
+    let border = |cell: i64, step: i64, start: f32, origin: f32| {
+        let next = if step > 0 { cell + 1 } else { cell };
+        origin + next as f32 * grid.cell_size - start
+    };
+    // and how far along it the next border on each axis lies
+    let mut t_x = if step_x == 0 {
+        f32::MAX
+    } else {
+        border(column, step_x, line.origin.x, grid.bounds.x1()) / dx
+    };
+    let mut t_y = if step_y == 0 {
+        f32::MAX
+    } else {
+        border(row, step_y, line.origin.y, grid.bounds.y1()) / dy
+    };
+    push(column, row);
+    let steps = (last_column - column).abs() + (last_row - row).abs();
+    let mut taken = 0;
+    while (column, row) != (last_column, last_row) && taken < steps {
+        if (t_x - t_y).abs() < 1e-6 {
+            // through the corner, into both neighbours and on diagonally
+            push(column + step_x, row);
+            push(column, row + step_y);
+            column += step_x;
+            row += step_y;
+            t_x += delta_x;
+            t_y += delta_y;
+            taken += 2;
+        } else if t_x < t_y {
+            column += step_x;
+            t_x += delta_x;
+            taken += 1;
+        } else {
+            row += step_y;
+            t_y += delta_y;
+            taken += 1;
+        }
+        push(column, row);
+    }
+    cells
+}
+
+// marks the supercover of the segment
+pub fn draw_line(line: Line, grid: &mut Grid<bool>) {
+    for (column, row) in supercover(line, grid) {
+        grid.cells[row * grid.columns + column] = true;
+    }
+}
+
+fn sign(value: f32) -> i64 {
+    if value > 0.0 {
+        1
+    } else if value < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+// Sutherland-Hodgman against each side of the box. Concave outlines can leave edges
+// running along the box, they add no area.
+fn clip_to_box(vertices: &[Point], bounds: BoundingBox) -> Vec<Point> {
+    let (x1, y1, x2, y2) = (bounds.x1(), bounds.y1(), bounds.x2(), bounds.y2());
+    let mut clipped = vertices.to_vec();
+    clipped = clip_side(&clipped, |point| point.x >= x1, |a, b| at_x(a, b, x1));
+    clipped = clip_side(&clipped, |point| point.x <= x2, |a, b| at_x(a, b, x2));
+    clipped = clip_side(&clipped, |point| point.y >= y1, |a, b| at_y(a, b, y1));
+    clip_side(&clipped, |point| point.y <= y2, |a, b| at_y(a, b, y2))
+}
+
+fn clip_side<I, C>(vertices: &[Point], inside: I, crossing: C) -> Vec<Point>
+where
+    I: Fn(Point) -> bool,
+    C: Fn(Point, Point) -> Point,
+{
+    let mut result = Vec::with_capacity(vertices.len() + 2);
+    for side in Edges::new(vertices) {
+        let (a, b) = (side.origin, side.end);
+        match (inside(a), inside(b)) {
+            (true, true) => result.push(b),
+            (true, false) => result.push(crossing(a, b)),
+            (false, true) => {
+                result.push(crossing(a, b));
+                result.push(b);
+            }
+            (false, false) => (),
+        }
+    }
+    result
+}
+
+fn at_x(a: Point, b: Point, x: f32) -> Point {
+    Point::new(x, a.y + (x - a.x) / (b.x - a.x) * (b.y - a.y))
+}
+
+fn at_y(a: Point, b: Point, y: f32) -> Point {
+    Point::new(a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x), y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::raster::*;
+    use crate::base::{Angle, Line, Point, Size};
+    use crate::collision::BoundingBox;
+    use crate::shape::{Circle, Polygon, Rectangle, Shape, Tessellation};
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::new(BoundingBox::from_edges(-2.0, 0.0, 3.0, 2.0), 1.0, 0u8);
+        assert_eq!((grid.columns(), grid.rows()), (5, 2));
+        assert_eq!(grid.cell_of(Point::new(-1.5, 1.5)), Some((0, 1)));
+        assert_eq!(grid.cell_of(Point::new(3.5, 1.5)), None);
+        assert_eq!(grid.cell_center(4, 0), Point::new(2.5, 0.5));
+        assert_eq!(
+            grid.cell_bounds(1, 1),
+            BoundingBox::from_edges(-1.0, 1.0, 0.0, 2.0)
+        );
+        assert_eq!(grid.get(5, 0), None);
+        // the last column sticks out past an uneven width
+        let uneven = Grid::new(BoundingBox::from_edges(0.0, 0.0, 2.5, 1.0), 1.0, 0u8);
+        assert_eq!(uneven.columns(), 3);
+    }
+    #[test]
+    fn test_occupancy() {
+        let bounds = BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0);
+        let mut grid = Grid::new(bounds, 1.0, false);
+        let square = Rectangle::new(Point::new(3.0, 3.0), Size::new(4.0, 2.0), Angle::zero());
+        occupancy(&square, &mut grid);
+        assert_eq!(grid.cells().iter().filter(|&&cell| cell).count(), 8);
+        assert_eq!(grid.get(1, 2), Some(&true));
+        assert_eq!(grid.get(1, 4), Some(&false));
+        // a second shape adds to the first, and may hang over the edge
+        let circle = Circle::with_tessellation(
+            Point::new(10.0, 10.0),
+            3.0,
+            Tessellation::new(0.01, 8, 512, Angle::zero()),
+        );
+        occupancy(&circle, &mut grid);
+        let count = grid.cells().iter().filter(|&&cell| cell).count();
+        let quarter = std::f32::consts::PI * 9.0 / 4.0;
+        assert!((count as f32 - 8.0 - quarter).abs() < 2.0);
+        assert_eq!(grid.get(1, 2), Some(&true));
+        // concave outlines leave their notch empty
+        let mut grid = Grid::new(bounds, 1.0, false);
+        let l_shape = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 4.0),
+            Point::new(0.0, 4.0),
+        ]);
+        occupancy(&l_shape, &mut grid);
+        assert_eq!(grid.cells().iter().filter(|&&cell| cell).count(), 7);
+        assert_eq!(grid.get(2, 2), Some(&false));
+    }
+    #[test]
+    fn test_coverage() {
+        let bounds = BoundingBox::from_edges(0.0, 0.0, 4.0, 4.0);
+        let mut grid = Grid::new(bounds, 1.0, 0.0);
+        // half a cell wide on both sides
+        let square = Rectangle::new(Point::new(2.0, 2.0), Size::new(2.0, 1.0), Angle::zero());
+        coverage(&square, &mut grid);
+        assert_eq!(grid.get(1, 1), Some(&0.5));
+        assert_eq!(grid.get(2, 2), Some(&0.5));
+        assert_eq!(grid.get(0, 2), Some(&0.0));
+        let total: f32 = grid.cells().iter().sum();
+        assert!((total - square.area()).abs() < 1e-4);
+        // a diamond splits its corner cells along the diagonal
+        let mut grid = Grid::new(bounds, 1.0, 0.0);
+        let diamond = Polygon::new(&[
+            Point::new(2.0, 0.0),
+            Point::new(4.0, 2.0),
+            Point::new(2.0, 4.0),
+            Point::new(0.0, 2.0),
+        ]);
+        coverage(&diamond, &mut grid);
+        assert_eq!(grid.get(0, 0), Some(&0.0));
+        assert_eq!(grid.get(1, 0), Some(&0.5));
+        assert_eq!(grid.get(1, 1), Some(&1.0));
+        let total: f32 = grid.cells().iter().sum();
+        assert!((total - 8.0).abs() < 1e-4);
+    }
+    #[test]
+    fn test_supercover() {
+        let grid = Grid::new(BoundingBox::from_edges(0.0, 0.0, 5.0, 5.0), 1.0, false);
+        let flat = supercover(Line::new(Point::new(0.5, 1.5), Point::new(3.5, 1.5)), &grid);
+        assert_eq!(flat, vec![(0, 1), (1, 1), (2, 1), (3, 1)]);
+        // through the corners, so the cells beside each one count as well
+        let diagonal = supercover(Line::new(Point::new(0.5, 0.5), Point::new(2.5, 2.5)), &grid);
+        assert_eq!(
+            diagonal,
+            vec![(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)]
+        );
+        // a shallow line touches every cell it crosses and nothing else
+        let shallow = supercover(Line::new(Point::new(0.2, 0.2), Point::new(4.8, 1.8)), &grid);
+        assert_eq!(shallow.first(), Some(&(0, 0)));
+        assert_eq!(shallow.last(), Some(&(4, 1)));
+        assert_eq!(shallow.len(), 6);
+        // backwards and partly outside the grid
+        let clipped = supercover(
+            Line::new(Point::new(2.5, 0.5), Point::new(-3.5, 0.5)),
+            &grid,
+        );
+        assert_eq!(clipped, vec![(2, 0), (1, 0), (0, 0)]);
+        let mut marked = grid.clone();
+        draw_line(
+            Line::new(Point::new(4.5, 4.5), Point::new(4.5, 2.5)),
+            &mut marked,
+        );
+        assert_eq!(marked.cells().iter().filter(|&&cell| cell).count(), 3);
+    }
+}
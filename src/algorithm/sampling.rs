@@ -0,0 +1,446 @@
+use std::f32::consts::PI;
+
+use crate::algorithm::triangulation::{to_triangles, triangulate};
+use crate::base::{Point, Triangle, Vector};
+use crate::collision::BoundingBox;
+use crate::shape::{Circle, Hexagon, PointLocation, Polygon, Rectangle, Shape};
+
+// candidates tried around an active point before it is retired, as in Bridson's paper
+const POISSON_ATTEMPTS: usize = 30;
+// draws in the bounds of a polygon before it is triangulated instead
+const REJECTION_ATTEMPTS: usize = 16;
+
+// Uniform numbers in [0, 1). Any closure returning them works, so a `rand` generator
+// can be passed as `|| rng.gen()`.
+pub trait RandomSource {
+    fn next_f32(&mut self) -> f32;
+}
+
+impl<F: FnMut() -> f32> RandomSource for F {
+    fn next_f32(&mut self) -> f32 {
+        self()
+    }
+}
+
+pub trait Sample {
+    fn bounds(&self) -> BoundingBox;
+    // inclusive of the boundary
+    fn contains(&self, point: Point) -> bool;
+    // uniform over the area, none for shapes without any
+    fn sample_inside<R: RandomSource>(&self, rng: &mut R) -> Option<Point>;
+    // uniform over the length of the outline
+    fn sample_boundary<R: RandomSource>(&self, rng: &mut R) -> Point;
+    fn samples_inside<R: RandomSource>(&self, count: usize, rng: &mut R) -> Vec<Point> {
+        (0..count).map_while(|_| self.sample_inside(rng)).collect()
+    }
+    fn samples_boundary<R: RandomSource>(&self, count: usize, rng: &mut R) -> Vec<Point> {
+        (0..count).map(|_| self.sample_boundary(rng)).collect()
+    }
+}
+
+// Blue noise inside the shape, no two points closer than `min_distance` and no room
+// left for another one. Uses Bridson's algorithm, so the cost grows with the number of
+// points returned. Empty for shapes without area.
+pub fn poisson_disk<S: Sample, R: RandomSource>(
+    shape: &S,
+    min_distance: f32,
+    rng: &mut R,
+) -> Vec<Point> {
+    assert!(min_distance > 0.0, "Expected a positive distance");
+    // a flat outline has no area and no valid bounds either
+    let first = match shape.sample_inside(rng) {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    let bounds = shape.bounds();
+    let cell = min_distance / 2f32.sqrt();
+    let columns = (bounds.width() / cell).ceil().max(1.0) as usize;
+    let rows = (bounds.height() / cell).ceil().max(1.0) as usize;
+    let cell_of = |point: Point| {
+        let column = ((point.x - bounds.x1()) / cell) as usize;
+        let row = ((point.y - bounds.y1()) / cell) as usize;
+        (column.min(columns - 1), row.min(rows - 1))
+    };
+    // each cell is small enough to hold at most one point
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let mut points = Vec::new();
+    let mut active = Vec::new();
+
+    let (column, row) = cell_of(first);
+    grid[row * columns + column] = Some(0);
+    points.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let index = ((rng.next_f32() * active.len() as f32) as usize).min(active.len() - 1);
+        let origin = points[active[index]];
+        let mut found = false;
+        for _ in 0..POISSON_ATTEMPTS {
+            // uniform over the ring between one and two distances
+            let radius = min_distance * (1.0 + 3.0 * rng.next_f32()).sqrt();
+            let phi = 2.0 * PI * rng.next_f32();
+            let candidate = origin + Vector::new(radius * phi.cos(), radius * phi.sin());
+            if candidate.x < bounds.x1()
+                || candidate.x > bounds.x2()
+                || candidate.y < bounds.y1()
+                || candidate.y > bounds.y2()
+                || !shape.contains(candidate)
+            {
+                continue;
+            }
+            let (column, row) = cell_of(candidate);
+            let crowded = (row.saturating_sub(2)..(row + 3).min(rows)).any(|r| {
+                (column.saturating_sub(2)..(column + 3).min(columns)).any(|c| {
+                    grid[r * columns + c]
+                        .is_some_and(|other| points[other].distance_to(candidate) < min_distance)
+                })
+            });
+            if !crowded {
+                grid[row * columns + column] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(index);
+        }
+    }
+    points
+}
+
+// The triangles of an outline with their running areas, to pick one by its share.
+struct TriangleTable {
+    triangles: Vec<Triangle>,
+    cumulative: Vec<f32>,
+}
+
+impl TriangleTable {
+    fn new(vertices: &[Point]) -> Self {
+        let triangles = to_triangles(vertices, &triangulate(vertices));
+        let cumulative = running_sum(triangles.iter().map(|triangle| triangle.area()));
+        Self {
+            triangles,
+            cumulative,
+        }
+    }
+    fn sample<R: RandomSource>(&self, rng: &mut R) -> Option<Point> {
+        let i = pick(&self.cumulative, rng)?;
+        Some(sample_triangle(&self.triangles[i], rng))
+    }
+}
+
+fn sample_triangle<R: RandomSource>(triangle: &Triangle, rng: &mut R) -> Point {
+    let (mut u, mut v) = (rng.next_f32(), rng.next_f32());
+    // folds the far half of the parallelogram back onto the triangle
+    if u + v > 1.0 {
+        u = 1.0 - u;
+        v = 1.0 - v;
+    }
+    triangle.a
+        + Vector::from_points(triangle.a, triangle.b) * u
+        + Vector::from_points(triangle.a, triangle.c) * v
+}
+
+fn running_sum(values: impl Iterator<Item = f32>) -> Vec<f32> {
+    let mut total = 0.0;
+    values
+        .map(|value| {
+            total += value;
+            total
+        })
+        .collect()
+}
+
+// an index drawn with the weights the running sum was built from, none if they are
+// all zero
+fn pick<R: RandomSource>(cumulative: &[f32], rng: &mut R) -> Option<usize> {
+    let total = cumulative.last().copied().unwrap_or(0.0);
+    if total <= 0.0 {
+        return None;
+    }
+    let target = rng.next_f32() * total;
+    Some(
+        cumulative
+            .partition_point(|&sum| sum <= target)
+            .min(cumulative.len() - 1),
+    )
+}
+
+fn sample_outline<R: RandomSource>(vertices: &[Point], rng: &mut R) -> Point {
+    assert!(!vertices.is_empty(), "Expected at least one vertex");
+    let n = vertices.len();
+    let lengths = running_sum((0..n).map(|i| vertices[i].distance_to(vertices[(i + 1) % n])));
+    // all vertices in one place
+    let i = match pick(&lengths, rng) {
+        Some(i) => i,
+        None => return vertices[0],
+    };
+    let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+    a + Vector::from_points(a, b) * rng.next_f32()
+}
+
+fn box_corners(bounds: &BoundingBox) -> [Point; 4] {
+    [
+        Point::new(bounds.x1(), bounds.y1()),
+        Point::new(bounds.x2(), bounds.y1()),
+        Point::new(bounds.x2(), bounds.y2()),
+        Point::new(bounds.x1(), bounds.y2()),
+    ]
+}
+
+impl Sample for Circle {
+    fn bounds(&self) -> BoundingBox {
+        self.bounding_box()
+    }
+    fn contains(&self, point: Point) -> bool {
+        point.distance_to(self.center()) <= self.radius()
+    }
+    fn sample_inside<R: RandomSource>(&self, rng: &mut R) -> Option<Point> {
+        if self.radius() <= 0.0 {
+            return None;
+        }
+        // the square root spreads the points evenly over the rings
+        let radius = self.radius() * rng.next_f32().sqrt();
+        let phi = 2.0 * PI * rng.next_f32();
+        Some(self.center() + Vector::new(radius * phi.cos(), radius * phi.sin()))
+    }
+    // on the true circle, not its tessellation
+    fn sample_boundary<R: RandomSource>(&self, rng: &mut R) -> Point {
+        let phi = 2.0 * PI * rng.next_f32();
+        self.center() + Vector::new(self.radius() * phi.cos(), self.radius() * phi.sin())
+    }
+}
+
+impl Sample for BoundingBox {
+    fn bounds(&self) -> BoundingBox {
+        *self
+    }
+    fn contains(&self, point: Point) -> bool {
+        point.x >= self.x1() && point.x <= self.x2() && point.y >= self.y1() && point.y <= self.y2()
+    }
+    fn sample_inside<R: RandomSource>(&self, rng: &mut R) -> Option<Point> {
+        if self.width() <= 0.0 || self.height() <= 0.0 {
+            return None;
+        }
+        Some(Point::new(
+            self.x1() + self.width() * rng.next_f32(),
+            self.y1() + self.height() * rng.next_f32(),
+        ))
+    }
+    fn sample_boundary<R: RandomSource>(&self, rng: &mut R) -> Point {
+        sample_outline(&box_corners(self), rng)
+    }
+}
+
+impl Sample for Rectangle {
+    fn bounds(&self) -> BoundingBox {
+        self.bounding_box()
+    }
+    fn contains(&self, point: Point) -> bool {
+        self.cached_polygon().locate(point) != PointLocation::Outside
+    }
+    fn sample_inside<R: RandomSource>(&self, rng: &mut R) -> Option<Point> {
+        if self.area() <= 0.0 {
+            return None;
+        }
+        // two sides from the first corner span the rectangle
+        let vertices = self.vertices();
+        let (corner, u, v) = (vertices[0], rng.next_f32(), rng.next_f32());
+        Some(
+            corner
+                + Vector::from_points(corner, vertices[1]) * u
+                + Vector::from_points(corner, vertices[3]) * v,
+        )
+    }
+    fn sample_boundary<R: RandomSource>(&self, rng: &mut R) -> Point {
+        sample_outline(self.vertices(), rng)
+    }
+}
+
+impl Sample for Hexagon {
+    fn bounds(&self) -> BoundingBox {
+        self.bounding_box()
+    }
+    fn contains(&self, point: Point) -> bool {
+        self.cached_polygon().locate(point) != PointLocation::Outside
+    }
+    fn sample_inside<R: RandomSource>(&self, rng: &mut R) -> Option<Point> {
+        if self.side() <= 0.0 {
+            return None;
+        }
+        // six equal triangles around the centre
+        let vertices = self.vertices();
+        let i = ((rng.next_f32() * 6.0) as usize).min(5);
+        let triangle = Triangle::new(self.center(), vertices[i], vertices[(i + 1) % 6]);
+        Some(sample_triangle(&triangle, rng))
+    }
+    fn sample_boundary<R: RandomSource>(&self, rng: &mut R) -> Point {
+        sample_outline(self.vertices(), rng)
+    }
+}
+
+// A single point is drawn in the bounds until one lands inside, which keeps it uniform.
+// Polygons filling too little of their bounds for that are triangulated instead, draw
+// several points with `samples_inside` to do that only once.
+impl Sample for Polygon {
+    fn bounds(&self) -> BoundingBox {
+        self.to_bounding_box()
+    }
+    fn contains(&self, point: Point) -> bool {
+        self.locate(point) != PointLocation::Outside
+    }
+    fn sample_inside<R: RandomSource>(&self, rng: &mut R) -> Option<Point> {
+        if self.area() <= 0.0 {
+            return None;
+        }
+        let bounds = self.bounds();
+        for _ in 0..REJECTION_ATTEMPTS {
+            let candidate = bounds.sample_inside(rng)?;
+            if self.contains(candidate) {
+                return Some(candidate);
+            }
+        }
+        TriangleTable::new(&self.vertices).sample(rng)
+    }
+    fn sample_boundary<R: RandomSource>(&self, rng: &mut R) -> Point {
+        sample_outline(&self.vertices, rng)
+    }
+    fn samples_inside<R: RandomSource>(&self, count: usize, rng: &mut R) -> Vec<Point> {
+        let table = TriangleTable::new(&self.vertices);
+        (0..count).map_while(|_| table.sample(rng)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::sampling::*;
+    use crate::base::{Angle, Point, Size};
+    use crate::collision::BoundingBox;
+    use crate::shape::{Circle, Hexagon, Polygon, Rectangle, Shape};
+
+    // xorshift, enough to make the tests repeatable
+    fn generator(seed: u32) -> impl FnMut() -> f32 {
+        let mut state = seed;
+        move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 8) as f32 / (1u32 << 24) as f32
+        }
+    }
+
+    fn l_shape() -> Polygon {
+        Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 4.0),
+            Point::new(0.0, 4.0),
+        ])
+    }
+
+    #[test]
+    fn test_inside() {
+        let mut rng = generator(7);
+        let circle = Circle::new(Point::new(5.0, 5.0), 2.0);
+        let rectangle = Rectangle::new(Point::zero(), Size::new(4.0, 2.0), Angle::new(30.0));
+        let hexagon = Hexagon::new(Point::new(-3.0, 1.0), 1.5, Angle::new(10.0));
+        let bounds = BoundingBox::from_edges(-1.0, -1.0, 3.0, 0.0);
+        for _ in 0..500 {
+            assert!(circle.contains(circle.sample_inside(&mut rng).unwrap()));
+            assert!(rectangle.contains(rectangle.sample_inside(&mut rng).unwrap()));
+            assert!(hexagon.contains(hexagon.sample_inside(&mut rng).unwrap()));
+            assert!(bounds.contains(bounds.sample_inside(&mut rng).unwrap()));
+        }
+        let polygon = l_shape();
+        for _ in 0..500 {
+            assert!(polygon.contains(polygon.sample_inside(&mut rng).unwrap()));
+        }
+        let points = polygon.samples_inside(2000, &mut rng);
+        assert!(points.iter().all(|&point| polygon.contains(point)));
+        // both arms hold the same area, so they get about as many points
+        let upper = points.iter().filter(|point| point.y > 1.0).count();
+        let right = points.iter().filter(|point| point.x > 1.0).count();
+        assert!(upper > 700 && right > 700);
+        // half of the area of a circle lies beyond 1/√2 of its radius
+        let circle_points = circle.samples_inside(2000, &mut rng);
+        let outer = circle_points
+            .iter()
+            .filter(|point| point.distance_to(circle.center()) > 2.0 / 2f32.sqrt())
+            .count();
+        assert!(outer > 900 && outer < 1100);
+    }
+    #[test]
+    fn test_no_area() {
+        let mut rng = generator(5);
+        let flat = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(4.0, 0.0),
+        ]);
+        assert_eq!(flat.sample_inside(&mut rng), None);
+        assert!(flat.samples_inside(10, &mut rng).is_empty());
+        assert!(poisson_disk(&flat, 0.5, &mut rng).is_empty());
+        assert_eq!(Polygon::default().sample_inside(&mut rng), None);
+        assert_eq!(
+            Circle::new(Point::zero(), 0.0).sample_inside(&mut rng),
+            None
+        );
+        // the outline itself can still be sampled
+        assert_eq!(flat.sample_boundary(&mut rng).y, 0.0);
+        let single = Polygon::new(&[Point::new(1.0, 1.0)]);
+        assert_eq!(single.sample_boundary(&mut rng), Point::new(1.0, 1.0));
+    }
+    #[test]
+    fn test_boundary() {
+        let mut rng = generator(11);
+        let circle = Circle::new(Point::new(1.0, 1.0), 3.0);
+        for point in circle.samples_boundary(100, &mut rng) {
+            assert!((point.distance_to(circle.center()) - 3.0).abs() < 1e-4);
+        }
+        let rectangle = Rectangle::new(Point::zero(), Size::new(4.0, 2.0), Angle::new(45.0));
+        for point in rectangle.samples_boundary(100, &mut rng) {
+            assert_eq!(rectangle.closest_point(point), point);
+        }
+        let polygon = l_shape();
+        for point in polygon.samples_boundary(100, &mut rng) {
+            assert_eq!(polygon.locate(point), PointLocation::Boundary);
+        }
+        let bounds = BoundingBox::from_edges(0.0, 0.0, 8.0, 1.0);
+        let points = bounds.samples_boundary(1000, &mut rng);
+        // the long sides make up eight ninths of the outline
+        let long = points
+            .iter()
+            .filter(|point| point.y == 0.0 || point.y == 1.0)
+            .count();
+        assert!(long > 830 && long < 950);
+        let hexagon = Hexagon::new(Point::zero(), 1.0, Angle::zero());
+        let point = hexagon.sample_boundary(&mut rng);
+        assert!(hexagon.contains(point) && !hexagon.polygon().is_inside(point));
+    }
+    #[test]
+    fn test_poisson_disk() {
+        let mut rng = generator(3);
+        let polygon = l_shape();
+        let points = poisson_disk(&polygon, 0.5, &mut rng);
+        assert!(points.iter().all(|&point| polygon.contains(point)));
+        for (i, a) in points.iter().enumerate() {
+            for b in points[i + 1..].iter() {
+                assert!(a.distance_to(*b) >= 0.5);
+            }
+        }
+        // seven square units at one point per disk of radius 0.25 or so
+        assert!(points.len() > 20 && points.len() < 60);
+        // no gap is left wide enough for another point
+        let probe = Point::new(0.5, 3.5);
+        assert!(points.iter().any(|point| point.distance_to(probe) < 1.0));
+
+        let circle = Circle::new(Point::zero(), 5.0);
+        let points = poisson_disk(&circle, 1.0, &mut rng);
+        assert!(points.iter().all(|&point| circle.contains(point)));
+        assert!(points.len() > 40);
+    }
+}
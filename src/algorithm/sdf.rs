@@ -0,0 +1,313 @@
+use crate::base::{Point, Vector};
+use crate::collision::{Hit, Ray};
+use crate::shape::{
+    central_gradient, AnyShape, Circle, CompoundShape, Hexagon, Polygon, Rectangle, Shape,
+    GRADIENT_STEP,
+};
+
+// steps before a sphere trace gives up, grazing rays can crawl along an outline
+const MAX_STEPS: usize = 256;
+
+// A signed distance field, negative inside. The combinators below keep it a lower
+// bound on the true distance, which is all sphere tracing needs, but it is only exact
+// for plain shapes.
+pub trait Sdf {
+    fn distance(&self, point: Point) -> f32;
+    // combined fields have no size to scale the step by, so they step as a unit shape
+    fn gradient(&self, point: Point) -> Vector {
+        central_gradient(|at| self.distance(at), point, GRADIENT_STEP)
+    }
+    fn union<B: Sdf>(self, other: B) -> Union<Self, B>
+    where
+        Self: Sized,
+    {
+        Union { a: self, b: other }
+    }
+    fn intersection<B: Sdf>(self, other: B) -> Intersection<Self, B>
+    where
+        Self: Sized,
+    {
+        Intersection { a: self, b: other }
+    }
+    // self with other cut away
+    fn subtract<B: Sdf>(self, other: B) -> Subtraction<Self, B>
+    where
+        Self: Sized,
+    {
+        Subtraction { a: self, b: other }
+    }
+    // the union with the seam filled over a width of about `k`
+    fn smooth_union<B: Sdf>(self, other: B, k: f32) -> SmoothUnion<Self, B>
+    where
+        Self: Sized,
+    {
+        SmoothUnion {
+            a: self,
+            b: other,
+            k,
+        }
+    }
+    // grown by `radius`, which also rounds off the corners
+    fn round(self, radius: f32) -> Rounded<Self>
+    where
+        Self: Sized,
+    {
+        Rounded {
+            inner: self,
+            radius,
+        }
+    }
+}
+
+impl<T: Sdf + ?Sized> Sdf for &T {
+    fn distance(&self, point: Point) -> f32 {
+        (**self).distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        (**self).gradient(point)
+    }
+}
+
+impl Sdf for Circle {
+    fn distance(&self, point: Point) -> f32 {
+        self.signed_distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        Shape::gradient(self, point)
+    }
+}
+
+impl Sdf for Rectangle {
+    fn distance(&self, point: Point) -> f32 {
+        self.signed_distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        Shape::gradient(self, point)
+    }
+}
+
+impl Sdf for Hexagon {
+    fn distance(&self, point: Point) -> f32 {
+        self.signed_distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        Shape::gradient(self, point)
+    }
+}
+
+impl Sdf for Polygon {
+    fn distance(&self, point: Point) -> f32 {
+        self.signed_distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        Shape::gradient(self, point)
+    }
+}
+
+impl Sdf for CompoundShape {
+    fn distance(&self, point: Point) -> f32 {
+        self.signed_distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        Shape::gradient(self, point)
+    }
+}
+
+impl Sdf for AnyShape {
+    fn distance(&self, point: Point) -> f32 {
+        self.signed_distance(point)
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        Shape::gradient(self, point)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Union<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A: Sdf, B: Sdf> Sdf for Union<A, B> {
+    fn distance(&self, point: Point) -> f32 {
+        self.a.distance(point).min(self.b.distance(point))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Intersection<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A: Sdf, B: Sdf> Sdf for Intersection<A, B> {
+    fn distance(&self, point: Point) -> f32 {
+        self.a.distance(point).max(self.b.distance(point))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Subtraction<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<A: Sdf, B: Sdf> Sdf for Subtraction<A, B> {
+    fn distance(&self, point: Point) -> f32 {
+        self.a.distance(point).max(-self.b.distance(point))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmoothUnion<A, B> {
+    pub a: A,
+    pub b: B,
+    pub k: f32,
+}
+
+impl<A: Sdf, B: Sdf> Sdf for SmoothUnion<A, B> {
+    fn distance(&self, point: Point) -> f32 {
+        smooth_min(self.a.distance(point), self.b.distance(point), self.k)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rounded<T> {
+    pub inner: T,
+    pub radius: f32,
+}
+
+impl<T: Sdf> Sdf for Rounded<T> {
+    fn distance(&self, point: Point) -> f32 {
+        self.inner.distance(point) - self.radius
+    }
+}
+
+// The polynomial smooth minimum, it matches `min` once a and b are `k` apart and dips
+// at most k / 4 below it where they meet.
+pub fn smooth_min(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (k - (a - b).abs()).max(0.0) / k;
+    a.min(b) - h * h * k / 4.0
+}
+
+// Marches along the ray by the distance to the field until it is within `tolerance`
+// of the surface. The hit has the same form `collide` gives for a ray, a ray starting
+// inside hits at once.
+pub fn sphere_trace<S: Sdf + ?Sized>(sdf: &S, ray: Ray, tolerance: f32) -> Option<Hit> {
+    assert!(tolerance > 0.0, "Expected a positive tolerance");
+    if ray.length <= 0.0 {
+        return None;
+    }
+    let direction = ray.vector() / ray.length;
+    let mut travelled = 0.0;
+    for _ in 0..MAX_STEPS {
+        let position = ray.origin + direction * travelled;
+        let distance = sdf.distance(position);
+        if distance < tolerance {
+            let time = travelled / ray.length;
+            return Some(Hit::new_time(
+                position,
+                sdf.gradient(position),
+                -ray.vector() * (1.0 - time),
+                time,
+            ));
+        }
+        travelled += distance;
+        if travelled > ray.length {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::sdf::*;
+    use crate::base::{Angle, Point, Size, Vector};
+    use crate::collision::{collide, Ray};
+    use crate::shape::{Circle, Hexagon, Polygon, Rectangle, Shape};
+
+    #[test]
+    fn test_signed_distance() {
+        let circle = Circle::new(Point::new(1.0, 1.0), 2.0);
+        assert_eq!(circle.signed_distance(Point::new(1.0, 2.0)), -1.0);
+        assert_eq!(circle.signed_distance(Point::new(1.0, 4.0)), 1.0);
+        assert_eq!(
+            Shape::gradient(&circle, Point::new(4.0, 1.0)),
+            Vector::new(1.0, 0.0)
+        );
+        let rectangle = Rectangle::new(Point::zero(), Size::new(4.0, 2.0), Angle::new(90.0));
+        assert_eq!(rectangle.signed_distance(Point::new(0.5, 0.0)), -0.5);
+        assert_eq!(rectangle.signed_distance(Point::new(0.0, 3.0)), 1.0);
+        // beyond a corner the distance is to the corner itself
+        assert_eq!(rectangle.signed_distance(Point::new(4.0, 6.0)), 5.0);
+        let gradient = Shape::gradient(&rectangle, Point::new(0.0, -2.5));
+        assert!((gradient - Vector::new(0.0, -1.0)).magnitude() < 1e-3);
+        // the field steps as the shape does
+        let large = Rectangle::new(Point::zero(), Size::new(4e4, 2e4), Angle::new(30.0));
+        let point = Point::new(1e4, 3e4);
+        assert_eq!(Sdf::gradient(&large, point), Shape::gradient(&large, point));
+        let hexagon = Hexagon::new(Point::zero(), 2.0, Angle::zero());
+        // the apothem is √3 for a side of two
+        assert!((hexagon.signed_distance(Point::zero()) + 3f32.sqrt()).abs() < 1e-5);
+        assert!(hexagon.signed_distance(Point::new(2.0, 0.0)).abs() < 1e-5);
+        // either winding gives the same sign
+        let mut square = Rectangle::new(Point::zero(), Size::new(2.0, 2.0), Angle::zero())
+            .polygon()
+            .vertices;
+        square.reverse();
+        assert_eq!(
+            Polygon::new(&square).signed_distance(Point::new(0.5, 0.0)),
+            -0.5
+        );
+    }
+    #[test]
+    fn test_combinators() {
+        let a = Circle::new(Point::new(-1.0, 0.0), 2.0);
+        let b = Circle::new(Point::new(1.0, 0.0), 2.0);
+        let point = Point::new(-3.5, 0.0);
+        assert_eq!((&a).union(&b).distance(point), 0.5);
+        assert_eq!((&a).intersection(&b).distance(point), 2.5);
+        assert_eq!((&a).subtract(&b).distance(Point::new(0.0, 0.0)), 1.0);
+        assert_eq!((&a).subtract(&b).distance(Point::new(-2.0, 0.0)), -1.0);
+        assert_eq!((&a).round(0.5).distance(point), 0.0);
+        // the smooth union bulges out where the two meet
+        let seam = Point::new(0.0, 2.0);
+        let plain = (&a).union(&b).distance(seam);
+        let smooth = (&a).smooth_union(&b, 1.0).distance(seam);
+        assert!(smooth < plain && smooth >= plain - 0.25);
+        assert_eq!((&a).smooth_union(&b, 1.0).distance(point), 0.5);
+        assert_eq!(smooth_min(1.0, 3.0, 1.0), 1.0);
+        assert_eq!(smooth_min(1.0, 1.0, 1.0), 0.75);
+        assert_eq!(smooth_min(1.0, 1.0, 0.0), 1.0);
+    }
+    #[test]
+    fn test_sphere_trace() {
+        let square = Rectangle::new(Point::new(5.0, 0.0), Size::new(2.0, 2.0), Angle::zero());
+        let ray = Ray::new(Point::zero(), Angle::zero(), 10.0);
+        let hit = sphere_trace(&square, ray, 1e-4).unwrap();
+        let expected = collide(&ray.into(), &square.clone().into()).unwrap();
+        assert!(hit.contact.distance_to(expected.contact) < 1e-3);
+        assert!((hit.time - expected.time).abs() < 1e-3);
+        assert!((hit.normal - expected.normal).magnitude() < 1e-3);
+        assert!(sphere_trace(
+            &square,
+            Ray::new(Point::zero(), Angle::new(90.0), 10.0),
+            1e-4
+        )
+        .is_none());
+        assert!(sphere_trace(&square, Ray::new(Point::zero(), Angle::zero(), 3.0), 1e-4).is_none());
+        // the hole lets the ray through to the far side of the ring
+        let ring =
+            Circle::new(Point::new(5.0, 0.0), 3.0).subtract(Circle::new(Point::new(5.0, 0.0), 2.0));
+        let inner = Ray::new(Point::new(5.0, 0.0), Angle::zero(), 5.0);
+        let hit = sphere_trace(&ring, inner, 1e-4).unwrap();
+        assert!((hit.contact.x - 7.0).abs() < 1e-3);
+        assert!((hit.normal - Vector::new(-1.0, 0.0)).magnitude() < 1e-2);
+        let outer = sphere_trace(&ring, ray, 1e-4).unwrap();
+        assert!((outer.contact.x - 2.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,401 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::algorithm::validation::orient;
+use crate::base::{Line, Point, Triangle};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    // largest distance a removed vertex may have from the simplified outline for
+    // Douglas-Peucker, smallest triangle area kept for Visvalingam-Whyatt
+    Tolerance(f32),
+    VertexCount(usize),
+}
+
+// Refines from the end points (or two far apart vertices of a ring), always splitting
+// the span with the largest deviation first. Keeping topology may need more vertices
+// than a vertex count target asks for.
+pub fn douglas_peucker(
+    points: &[Point],
+    closed: bool,
+    target: Target,
+    preserve_topology: bool,
+) -> Vec<Point> {
+    let n = points.len();
+    let minimum = if closed { 3 } else { 2 };
+    if n <= minimum {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    let mut spans = BinaryHeap::new();
+    if closed {
+        let far = (1..n)
+            .max_by(|&a, &b| {
+                let da = points[a].squared_distance_to(points[0]);
+                let db = points[b].squared_distance_to(points[0]);
+                da.partial_cmp(&db).unwrap()
+            })
+            .unwrap();
+        keep[far] = true;
+        spans.extend(Span::new(points, 0, far));
+        spans.extend(Span::new(points, far, n));
+    } else {
+        keep[n - 1] = true;
+        spans.extend(Span::new(points, 0, n - 1));
+    }
+    let mut count = 2;
+    while let Some(span) = spans.pop() {
+        let done = match target {
+            Target::Tolerance(tolerance) => span.deviation <= tolerance,
+            Target::VertexCount(target) => count >= target,
+        };
+        if done && count >= minimum {
+            break;
+        }
+        keep[span.farthest % n] = true;
+        count += 1;
+        spans.extend(Span::new(points, span.start, span.farthest));
+        spans.extend(Span::new(points, span.farthest, span.end));
+    }
+    if preserve_topology {
+        // split every span whose chord crosses another until the outline is simple again
+        loop {
+            let kept: Vec<usize> = (0..n).filter(|&i| keep[i]).collect();
+            let mut chords: Vec<(usize, usize)> = kept.windows(2).map(|w| (w[0], w[1])).collect();
+            if closed {
+                chords.push((kept[kept.len() - 1], n));
+            }
+            let ring: Vec<Point> = kept.iter().map(|&i| points[i]).collect();
+            let crossing = crossing_edges(&ring, closed);
+            let mut refined = false;
+            for idx in crossing {
+                let (start, end) = chords[idx];
+                if let Some(span) = Span::new(points, start, end) {
+                    keep[span.farthest % n] = true;
+                    refined = true;
+                }
+            }
+            if !refined {
+                break;
+            }
+        }
+    }
+    (0..n).filter(|&i| keep[i]).map(|i| points[i]).collect()
+}
+
+// Repeatedly drops the vertex spanning the smallest triangle with its neighbours.
+pub fn visvalingam_whyatt(
+    points: &[Point],
+    closed: bool,
+    target: Target,
+    preserve_topology: bool,
+) -> Vec<Point> {
+    let n = points.len();
+    let minimum = if closed { 3 } else { 2 };
+    if n <= minimum {
+        return points.to_vec();
+    }
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let mut alive = vec![true; n];
+    let mut version = vec![0usize; n];
+    let mut heap = BinaryHeap::new();
+    let removable = |i: usize| closed || (i != 0 && i != n - 1);
+    for i in (0..n).filter(|&i| removable(i)) {
+        let area = Triangle::new(points[prev[i]], points[i], points[next[i]]).area();
+        heap.push(Candidate::new(area, i, 0));
+    }
+    let mut count = n;
+    let mut floor = 0f32;
+    while count > minimum {
+        let candidate = match heap.pop() {
+            Some(candidate) => candidate,
+            None => break,
+        };
+        let i = candidate.index;
+        if !alive[i] || candidate.version != version[i] {
+            continue;
+        }
+        let done = match target {
+            Target::Tolerance(tolerance) => candidate.area > tolerance,
+            Target::VertexCount(target) => count <= target,
+        };
+        if done {
+            break;
+        }
+        if preserve_topology && would_cross(points, &alive, &next, prev[i], i, next[i]) {
+            // reconsidered once one of its neighbours goes
+            continue;
+        }
+        alive[i] = false;
+        count -= 1;
+        // areas never shrink below the last removal, so vertices go in a stable order
+        floor = floor.max(candidate.area);
+        let (p, q) = (prev[i], next[i]);
+        next[p] = q;
+        prev[q] = p;
+        for &j in [p, q].iter() {
+            if removable(j) {
+                version[j] += 1;
+                let area = Triangle::new(points[prev[j]], points[j], points[next[j]]).area();
+                heap.push(Candidate::new(area.max(floor), j, version[j]));
+            }
+        }
+    }
+    (0..n).filter(|&i| alive[i]).map(|i| points[i]).collect()
+}
+
+// A run of vertices between two kept ones, `end` may run past the last index of a ring.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    start: usize,
+    end: usize,
+    farthest: usize,
+    deviation: f32,
+}
+
+impl Span {
+    fn new(points: &[Point], start: usize, end: usize) -> Option<Self> {
+        if end <= start + 1 {
+            return None;
+        }
+        let n = points.len();
+        let chord = Line::new(points[start % n], points[end % n]);
+        let mut best = Self {
+            start,
+            end,
+            farthest: start + 1,
+            deviation: -1.0,
+        };
+        for k in start + 1..end {
+            let deviation = chord
+                .closest_point(points[k % n])
+                .distance_to(points[k % n]);
+            if deviation > best.deviation {
+                best.farthest = k;
+                best.deviation = deviation;
+            }
+        }
+        Some(best)
+    }
+}
+
+impl PartialEq for Span {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Span {}
+
+impl PartialOrd for Span {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Span {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deviation
+            .partial_cmp(&other.deviation)
+            .unwrap_or(Ordering::Equal)
+            .then(other.start.cmp(&self.start))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    area: f32,
+    index: usize,
+    version: usize,
+}
+
+impl Candidate {
+    fn new(area: f32, index: usize, version: usize) -> Self {
+        Self {
+            area,
+            index,
+            version,
+        }
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// reversed, the binary heap pops the smallest area first
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+// true if replacing a -> i -> b by a -> b crosses the remaining outline
+fn would_cross(
+    points: &[Point],
+    alive: &[bool],
+    next: &[usize],
+    a: usize,
+    i: usize,
+    b: usize,
+) -> bool {
+    let triangle = Triangle::new(points[a], points[i], points[b]);
+    for j in (0..points.len()).filter(|&j| alive[j] && j != i) {
+        let k = next[j];
+        if j != a && j != b && strictly_inside(&triangle, points[j]) {
+            return true;
+        }
+        if j == i || k == i || j == a || j == b || k == a || k == b {
+            continue;
+        }
+        if segments_intersect(points[a], points[b], points[j], points[k]) {
+            return true;
+        }
+    }
+    false
+}
+
+fn strictly_inside(triangle: &Triangle, point: Point) -> bool {
+    let signs = [
+        orient(triangle.a, triangle.b, point),
+        orient(triangle.b, triangle.c, point),
+        orient(triangle.c, triangle.a, point),
+    ];
+    signs.iter().all(|&s| s > 0.0) || signs.iter().all(|&s| s < 0.0)
+}
+
+// indices of edges i -> i + 1 that touch a non adjacent edge
+fn crossing_edges(ring: &[Point], closed: bool) -> Vec<usize> {
+    let n = ring.len();
+    let edges = if closed { n } else { n - 1 };
+    let mut result = Vec::new();
+    for i in 0..edges {
+        for j in i + 1..edges {
+            let adjacent = j == i + 1 || (closed && i == 0 && j == n - 1);
+            if adjacent {
+                continue;
+            }
+            if segments_intersect(ring[i], ring[(i + 1) % n], ring[j], ring[(j + 1) % n]) {
+                result.push(i);
+                result.push(j);
+            }
+        }
+    }
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+// closed segments, touching counts
+fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let (d1, d2) = (orient(c, d, a), orient(c, d, b));
+    let (d3, d4) = (orient(a, b, c), orient(a, b, d));
+    if d1 * d2 > 0.0 || d3 * d4 > 0.0 {
+        return false;
+    }
+    if d1 == 0.0 && d2 == 0.0 {
+        let overlap = |p: f32, q: f32, r: f32, s: f32| p.max(q) >= r.min(s) && r.max(s) >= p.min(q);
+        return overlap(a.x, b.x, c.x, d.x) && overlap(a.y, b.y, c.y, d.y);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::simplify::*;
+    use crate::base::Point;
+    use crate::shape::Polygon;
+    use std::f32::consts::PI;
+
+    fn noisy_line() -> Vec<Point> {
+        (0..=20)
+            .map(|i| Point::new(i as f32, if i % 2 == 0 { 0.0 } else { 0.1 }))
+            .collect()
+    }
+
+    #[test]
+    fn test_douglas_peucker_open() {
+        let points = noisy_line();
+        let result = douglas_peucker(&points, false, Target::Tolerance(0.2), false);
+        assert_eq!(result, vec![Point::new(0.0, 0.0), Point::new(20.0, 0.0)]);
+        let result = douglas_peucker(&points, false, Target::Tolerance(0.05), false);
+        assert_eq!(result.len(), points.len());
+        let result = douglas_peucker(&points, false, Target::VertexCount(5), false);
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[0], points[0]);
+        assert_eq!(result[4], points[20]);
+    }
+    #[test]
+    fn test_visvalingam_whyatt_open() {
+        let points = noisy_line();
+        // the last triangle spans the whole line, with an area of 20 * 0.1 / 2
+        let result = visvalingam_whyatt(&points, false, Target::Tolerance(0.2), false);
+        assert!(result.len() > 2 && result.len() < 10);
+        let result = visvalingam_whyatt(&points, false, Target::Tolerance(1.5), false);
+        assert_eq!(result, vec![Point::new(0.0, 0.0), Point::new(20.0, 0.0)]);
+        let result = visvalingam_whyatt(&points, false, Target::VertexCount(7), false);
+        assert_eq!(result.len(), 7);
+    }
+    #[test]
+    fn test_closed() {
+        let circle = Polygon {
+            vertices: (0..64)
+                .map(|i| {
+                    let t = i as f32 * PI / 32.0;
+                    Point::new(10.0 * t.cos(), 10.0 * t.sin())
+                })
+                .collect(),
+        };
+        let count = circle.vertices.len();
+        for simplified in [
+            douglas_peucker(&circle.vertices, true, Target::VertexCount(8), false),
+            visvalingam_whyatt(&circle.vertices, true, Target::VertexCount(8), false),
+        ] {
+            assert_eq!(simplified.len(), 8);
+            let polygon = Polygon::new(&simplified);
+            assert!(polygon.signed_area() > 0.0);
+            assert!(polygon.area() > 0.85 * circle.area());
+        }
+        let result = douglas_peucker(&circle.vertices, true, Target::Tolerance(0.5), false);
+        assert!(result.len() > 3 && result.len() < count);
+        let result = douglas_peucker(&circle.vertices, true, Target::Tolerance(100.0), false);
+        assert_eq!(result.len(), 3);
+    }
+    #[test]
+    fn test_preserve_topology() {
+        // chords across the turns of a tight spiral cut through the neighbouring turn
+        let spiral: Vec<Point> = (0..120)
+            .map(|i| {
+                let t = i as f32 * 0.25;
+                let r = 1.0 + 0.4 * t;
+                Point::new(r * t.cos(), r * t.sin())
+            })
+            .collect();
+        assert!(crossing_edges(&spiral, false).is_empty());
+        let result = douglas_peucker(&spiral, false, Target::Tolerance(4.0), false);
+        assert!(!crossing_edges(&result, false).is_empty());
+        let result = douglas_peucker(&spiral, false, Target::Tolerance(4.0), true);
+        assert!(crossing_edges(&result, false).is_empty());
+        assert!(result.len() < spiral.len() / 2);
+        for &count in [5, 10, 20].iter() {
+            let result = visvalingam_whyatt(&spiral, false, Target::VertexCount(count), true);
+            assert!(crossing_edges(&result, false).is_empty());
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use crate::algorithm::validation::orient;
+use crate::base::{Point, Triangle};
+
+// Triangles are returned as counter-clockwise index triples into `vertices`, none at
+// all when the outline crosses itself.
+pub fn triangulate(vertices: &[Point]) -> Vec<[usize; 3]> {
+    triangulate_with_holes(vertices, &[])
+}
+
+// Indices refer to the outer vertices followed by the vertices of each hole in turn.
+pub fn triangulate_with_holes(outer: &[Point], holes: &[&[Point]]) -> Vec<[usize; 3]> {
+    if outer.len() < 3 {
+        return Vec::new();
+    }
+    let mut points = outer.to_vec();
+    let mut ring = oriented_ring(outer, 0, true);
+    let mut hole_rings = Vec::new();
+    for hole in holes.iter().filter(|hole| hole.len() >= 3) {
+        hole_rings.push(oriented_ring(hole, points.len(), false));
+        points.extend_from_slice(hole);
+    }
+    // bridging the rightmost holes first keeps the later bridges short
+    hole_rings.sort_by(|a, b| {
+        let max_x = |ring: &Vec<usize>| {
+            ring.iter()
+                .map(|&idx| points[idx].x)
+                .fold(f32::MIN, f32::max)
+        };
+        max_x(b).partial_cmp(&max_x(a)).unwrap()
+    });
+    for i in 0..hole_rings.len() {
+        let bridge = find_bridge(&points, &ring, &hole_rings[i], &hole_rings[i..]);
+        ring = splice(&ring, bridge, &hole_rings[i]);
+    }
+    ear_clip(&points, ring)
+}
+
+pub fn to_triangles(vertices: &[Point], indices: &[[usize; 3]]) -> Vec<Triangle> {
+    indices
+        .iter()
+        .map(|&[a, b, c]| Triangle::new(vertices[a], vertices[b], vertices[c]))
+        .collect()
+}
+
+fn oriented_ring(vertices: &[Point], offset: usize, ccw: bool) -> Vec<usize> {
+    let n = vertices.len();
+    let area: f64 = (0..n)
+        .map(|i| orient(Point::zero(), vertices[i], vertices[(i + 1) % n]))
+        .sum();
+    let mut ring: Vec<usize> = (offset..offset + n).collect();
+    if (area > 0.0) != ccw {
+        ring.reverse();
+    }
+    ring
+}
+
+// returns (position in the ring, position in the hole) of a bridge edge that
+// crosses neither the ring nor any hole still waiting to be bridged
+fn find_bridge(
+    points: &[Point],
+    ring: &[usize],
+    hole: &[usize],
+    pending: &[Vec<usize>],
+) -> (usize, usize) {
+    let hole_pos = (0..hole.len())
+        .max_by(|&a, &b| {
+            let (a, b) = (points[hole[a]], points[hole[b]]);
+            a.x.partial_cmp(&b.x)
+                .unwrap()
+                .then(b.y.partial_cmp(&a.y).unwrap())
+        })
+        .unwrap();
+    let target = points[hole[hole_pos]];
+    let mut candidates: Vec<usize> = (0..ring.len()).collect();
+    candidates.sort_by(|&a, &b| {
+        let da = points[ring[a]].squared_distance_to(target);
+        let db = points[ring[b]].squared_distance_to(target);
+        da.partial_cmp(&db).unwrap()
+    });
+    let n = ring.len();
+    let visible = |pos: usize| {
+        let start = points[ring[pos]];
+        if start == target {
+            return true;
+        }
+        let prev = points[ring[(pos + n - 1) % n]];
+        let next = points[ring[(pos + 1) % n]];
+        if !in_wedge(prev, start, next, target) {
+            return false;
+        }
+        let edges = std::iter::once(ring)
+            .chain(pending.iter().map(|ring| ring.as_slice()))
+            .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])));
+        for (a, b) in edges {
+            let (a, b) = (points[a], points[b]);
+            if a == start || b == start || a == target || b == target {
+                continue;
+            }
+            if segments_touch(start, target, a, b) {
+                return false;
+            }
+        }
+        true
+    };
+    let ring_pos = candidates
+        .iter()
+        .copied()
+        .find(|&pos| visible(pos))
+        .unwrap_or(candidates[0]);
+    (ring_pos, hole_pos)
+}
+
+// true if `point` is seen from the interior angle at `corner` of a counter-clockwise ring
+fn in_wedge(prev: Point, corner: Point, next: Point, point: Point) -> bool {
+    let left_of_incoming = orient(prev, corner, point) > 0.0;
+    let left_of_outgoing = orient(corner, next, point) > 0.0;
+    if orient(prev, corner, next) >= 0.0 {
+        left_of_incoming && left_of_outgoing
+    } else {
+        left_of_incoming || left_of_outgoing
+    }
+}
+
+fn segments_touch(p1: Point, p2: Point, q1: Point, q2: Point) -> bool {
+    let d1 = orient(q1, q2, p1);
+    let d2 = orient(q1, q2, p2);
+    let d3 = orient(p1, p2, q1);
+    let d4 = orient(p1, p2, q2);
+    if d1 * d2 > 0.0 || d3 * d4 > 0.0 {
+        return false;
+    }
+    if d1 == 0.0 && d2 == 0.0 {
+        // collinear, overlapping only if the projections overlap
+        let within = |a: f32, b: f32, c: f32, d: f32| a.max(b) >= c.min(d) && c.max(d) >= a.min(b);
+        return within(p1.x, p2.x, q1.x, q2.x) && within(p1.y, p2.y, q1.y, q2.y);
+    }
+    true
+}
+
+// walks ring[..=pos], then around the whole hole and back across the bridge
+fn splice(ring: &[usize], (ring_pos, hole_pos): (usize, usize), hole: &[usize]) -> Vec<usize> {
+    let mut result = Vec::with_capacity(ring.len() + hole.len() + 2);
+    result.extend_from_slice(&ring[..=ring_pos]);
+    for i in 0..=hole.len() {
+        result.push(hole[(hole_pos + i) % hole.len()]);
+    }
+    result.extend_from_slice(&ring[ring_pos..]);
+    result
+}
+
+// Empty when the ring is not simple, its triangles would overlap or fold over.
+fn ear_clip(points: &[Point], mut ring: Vec<usize>) -> Vec<[usize; 3]> {
+    let area: f64 = (0..ring.len())
+        .map(|i| {
+            orient(
+                Point::zero(),
+                points[ring[i]],
+                points[ring[(i + 1) % ring.len()]],
+            )
+        })
+        .sum();
+    let mut triangles = Vec::with_capacity(ring.len());
+    let mut i = 0;
+    let mut misses = 0;
+    while ring.len() > 3 {
+        let n = ring.len();
+        i %= n;
+        let (prev, cur, next) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+        let turn = orient(points[prev], points[cur], points[next]);
+        if turn == 0.0 {
+            // collinear or duplicate vertex, nothing to cover
+            ring.remove(i);
+            misses = 0;
+        } else if turn > 0.0 && is_ear(points, &ring, i) {
+            triangles.push([prev, cur, next]);
+            ring.remove(i);
+            misses = 0;
+        } else if misses >= n {
+            // no ear left, the input is not simple
+            return Vec::new();
+        } else {
+            i += 1;
+            misses += 1;
+        }
+    }
+    if ring.len() == 3 && orient(points[ring[0]], points[ring[1]], points[ring[2]]) > 0.0 {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+    // a crossing outline can still be clipped, but the triangles then cover more
+    // than the outline encloses
+    let covered: f64 = triangles
+        .iter()
+        .map(|&[a, b, c]| orient(points[a], points[b], points[c]))
+        .sum();
+    if (covered - area).abs() > 1e-6 * covered.abs().max(area.abs()).max(1.0) {
+        return Vec::new();
+    }
+    triangles
+}
+
+fn is_ear(points: &[Point], ring: &[usize], i: usize) -> bool {
+    let n = ring.len();
+    let triangle = Triangle::new(
+        points[ring[(i + n - 1) % n]],
+        points[ring[i]],
+        points[ring[(i + 1) % n]],
+    );
+    for offset in 2..n - 1 {
+        let point = points[ring[(i + offset) % n]];
+        if point == triangle.a || point == triangle.b || point == triangle.c {
+            continue;
+        }
+        if triangle.contains(point) {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::triangulation::*;
+    use crate::base::{Point, Triangle};
+    use crate::collision::BoundingBox;
+
+    fn total_area(triangles: &[Triangle]) -> f32 {
+        triangles
+            .iter()
+            .map(|triangle| triangle.signed_area())
+            .sum()
+    }
+
+    #[test]
+    fn test_convex() {
+        let vertices = BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners();
+        let indices = triangulate(&vertices);
+        assert_eq!(indices.len(), 2);
+        assert_eq!(total_area(&to_triangles(&vertices, &indices)), 4.0);
+    }
+    #[test]
+    fn test_concave() {
+        // comb shape with two notches from the top
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 0.0),
+            Point::new(5.0, 3.0),
+            Point::new(4.0, 3.0),
+            Point::new(4.0, 1.0),
+            Point::new(3.0, 1.0),
+            Point::new(3.0, 3.0),
+            Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 3.0),
+            Point::new(0.0, 3.0),
+        ];
+        let indices = triangulate(&vertices);
+        // vertices left collinear by clipping are dropped without a triangle
+        assert!(indices.len() <= vertices.len() - 2);
+        let triangles = to_triangles(&vertices, &indices);
+        assert!(triangles
+            .iter()
+            .all(|triangle| triangle.signed_area() > 0.0));
+        assert_eq!(total_area(&triangles), 15.0 - 4.0);
+    }
+    #[test]
+    fn test_clockwise_and_collinear() {
+        let mut vertices = BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0)
+            .corners()
+            .to_vec();
+        vertices.insert(1, Point::new(1.0, 0.0));
+        vertices.reverse();
+        let triangles = to_triangles(&vertices, &triangulate(&vertices));
+        assert!(triangles
+            .iter()
+            .all(|triangle| triangle.signed_area() > 0.0));
+        assert_eq!(total_area(&triangles), 4.0);
+    }
+    #[test]
+    fn test_holes() {
+        let outer = BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners();
+        let first = BoundingBox::from_edges(2.0, 2.0, 4.0, 4.0).corners();
+        let mut second = BoundingBox::from_edges(6.0, 5.0, 8.0, 8.0)
+            .corners()
+            .to_vec();
+        second.reverse();
+        let indices = triangulate_with_holes(&outer, &[&first, &second]);
+        let mut points = outer.to_vec();
+        points.extend(first);
+        points.extend(second);
+        let triangles = to_triangles(&points, &indices);
+        assert!(triangles
+            .iter()
+            .all(|triangle| triangle.signed_area() > 0.0));
+        assert!((total_area(&triangles) - (100.0 - 4.0 - 6.0)).abs() < 1e-4);
+        // nothing covers the holes
+        for triangle in triangles.iter() {
+            assert!(!triangle.contains(Point::new(3.0, 3.0)));
+            assert!(!triangle.contains(Point::new(7.0, 6.5)));
+        }
+    }
+    #[test]
+    fn test_degenerate() {
+        assert!(triangulate(&[Point::new(0.0, 0.0), Point::new(1.0, 0.0)]).is_empty());
+        let line = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+        ];
+        assert!(triangulate(&line).is_empty());
+    }
+    #[test]
+    fn test_self_intersecting() {
+        // a bow-tie, both wings would be clipped as overlapping triangles
+        let bow_tie = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.0, 2.0),
+        ];
+        assert!(triangulate(&bow_tie).is_empty());
+        let mut uneven = bow_tie.clone();
+        uneven[1] = Point::new(4.0, 2.0);
+        uneven[2] = Point::new(4.0, 0.0);
+        assert!(triangulate(&uneven).is_empty());
+    }
+}
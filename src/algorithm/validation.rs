@@ -0,0 +1,304 @@
+use crate::algorithm::boolean::{resolve, FillRule};
+use crate::base::{Point, Vector};
+use crate::shape::{MultiPolygon, Polygon, PolygonWithHoles};
+
+const EPSILON: f64 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    // no enclosed area, all vertices on one line
+    Degenerate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelfIntersection {
+    // indices of the edges starting at these vertices
+    pub edges: (usize, usize),
+    pub point: Point,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationReport {
+    pub orientation: Orientation,
+    // vertices repeating an earlier one, consecutive repeats are zero length edges instead
+    pub duplicate_vertices: Vec<usize>,
+    pub collinear_vertices: Vec<usize>,
+    pub zero_length_edges: Vec<usize>,
+    pub self_intersections: Vec<SelfIntersection>,
+    pub is_convex: bool,
+}
+
+impl ValidationReport {
+    // collinear vertices are redundant but do not make a polygon invalid
+    pub fn is_valid(&self) -> bool {
+        self.orientation != Orientation::Degenerate
+            && self.duplicate_vertices.is_empty()
+            && self.zero_length_edges.is_empty()
+            && self.self_intersections.is_empty()
+    }
+}
+
+pub fn orientation(vertices: &[Point]) -> Orientation {
+    let n = vertices.len();
+    let mut sum = 0.0;
+    for i in 0..n {
+        let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+        sum += a.x as f64 * b.y as f64 - b.x as f64 * a.y as f64;
+    }
+    if sum > 0.0 {
+        Orientation::CounterClockwise
+    } else if sum < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Degenerate
+    }
+}
+
+// twice the signed area of the triangle abc in f64, positive when it turns left
+pub(crate) fn orient(a: Point, b: Point, c: Point) -> f64 {
+    (b.x as f64 - a.x as f64) * (c.y as f64 - a.y as f64)
+        - (b.y as f64 - a.y as f64) * (c.x as f64 - a.x as f64)
+}
+
+pub fn validate(vertices: &[Point]) -> ValidationReport {
+    let n = vertices.len();
+    let orientation = if n < 3 {
+        Orientation::Degenerate
+    } else {
+        orientation(vertices)
+    };
+    let duplicate_vertices = (0..n)
+        .filter(|&j| {
+            (0..j).any(|i| vertices[i] == vertices[j] && i + 1 != j && !(i == 0 && j == n - 1))
+        })
+        .collect();
+    let zero_length_edges: Vec<usize> = (0..n)
+        .filter(|&i| n > 1 && vertices[i] == vertices[(i + 1) % n])
+        .collect();
+    let mut collinear_vertices = Vec::new();
+    let mut turns = Vec::new();
+    for i in 0..n {
+        let (prev, next) = (vertices[(i + n - 1) % n], vertices[(i + 1) % n]);
+        if prev == vertices[i] || next == vertices[i] {
+            continue;
+        }
+        let turn = turn(prev, vertices[i], next);
+        if turn == 0.0 {
+            collinear_vertices.push(i);
+        } else {
+            turns.push(turn);
+        }
+    }
+    let mut self_intersections = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+            if adjacent || zero_length_edges.contains(&i) || zero_length_edges.contains(&j) {
+                continue;
+            }
+            let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+            let (c, d) = (vertices[j], vertices[(j + 1) % n]);
+            if let Some(point) = segment_intersection(a, b, c, d) {
+                self_intersections.push(SelfIntersection {
+                    edges: (i, j),
+                    point,
+                });
+            }
+        }
+    }
+    let sign = if orientation == Orientation::Clockwise {
+        -1.0
+    } else {
+        1.0
+    };
+    let is_convex = orientation != Orientation::Degenerate
+        && self_intersections.is_empty()
+        && turns.iter().all(|&turn| turn * sign > 0.0);
+    ValidationReport {
+        orientation,
+        duplicate_vertices,
+        collinear_vertices,
+        zero_length_edges,
+        self_intersections,
+        is_convex,
+    }
+}
+
+// Drops zero length edges and collinear vertices, then splits self-intersecting
+// outlines into simple counter-clockwise parts, the area wound around in either
+// direction stays filled. None if the parts could not be resolved, see
+// `boolean::resolve`.
+pub fn make_valid(vertices: &[Point]) -> Option<MultiPolygon> {
+    let ring = clean(vertices);
+    if ring.len() < 3 {
+        return Some(MultiPolygon::default());
+    }
+    let report = validate(&ring);
+    if report.is_valid() {
+        let mut outer = Polygon { vertices: ring };
+        if report.orientation == Orientation::Clockwise {
+            outer.vertices.reverse();
+        }
+        return Some(MultiPolygon::new(vec![PolygonWithHoles::new(
+            outer,
+            Vec::new(),
+        )]));
+    }
+    resolve(&[Polygon { vertices: ring }], FillRule::NonZero)
+}
+
+// repeated until stable, removing a vertex can leave its neighbours collinear
+fn clean(vertices: &[Point]) -> Vec<Point> {
+    let mut ring = vertices.to_vec();
+    loop {
+        let n = ring.len();
+        if n < 3 {
+            return ring;
+        }
+        let redundant = (0..n).find(|&i| {
+            let (prev, next) = (ring[(i + n - 1) % n], ring[(i + 1) % n]);
+            ring[i] == next || turn(prev, ring[i], next) == 0.0
+        });
+        match redundant {
+            Some(i) => {
+                ring.remove(i);
+            }
+            None => return ring,
+        }
+    }
+}
+
+// sine of the turn angle at b, zero when within the tolerance of a straight line or a reversal
+fn turn(a: Point, b: Point, c: Point) -> f64 {
+    let (u, v) = (Vector::from_points(a, b), Vector::from_points(b, c));
+    let length = u.magnitude() as f64 * v.magnitude() as f64;
+    let sine = orient(a, b, c) / length;
+    if sine.abs() < EPSILON {
+        0.0
+    } else {
+        sine
+    }
+}
+
+// closed segments, an overlap reports the first end point inside the other segment
+fn segment_intersection(a: Point, b: Point, c: Point, d: Point) -> Option<Point> {
+    let (d1, d2) = (orient(c, d, a), orient(c, d, b));
+    let (d3, d4) = (orient(a, b, c), orient(a, b, d));
+    if d1 * d2 > 0.0 || d3 * d4 > 0.0 {
+        return None;
+    }
+    if d1 == 0.0 && d2 == 0.0 {
+        let within = |p: Point, s: Point, e: Point| {
+            p.x >= s.x.min(e.x) && p.x <= s.x.max(e.x) && p.y >= s.y.min(e.y) && p.y <= s.y.max(e.y)
+        };
+        return [(c, a, b), (d, a, b), (a, c, d), (b, c, d)]
+            .iter()
+            .find(|&&(p, s, e)| within(p, s, e))
+            .map(|&(p, _, _)| p);
+    }
+    let t = d1 / (d1 - d2);
+    Some(Point::new(
+        (a.x as f64 + t * (b.x as f64 - a.x as f64)) as f32,
+        (a.y as f64 + t * (b.y as f64 - a.y as f64)) as f32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::validation::*;
+    use crate::base::Point;
+    use crate::collision::BoundingBox;
+
+    #[test]
+    fn test_valid() {
+        let report = validate(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners());
+        assert!(report.is_valid());
+        assert!(report.is_convex);
+        assert_eq!(report.orientation, Orientation::CounterClockwise);
+        let mut vertices = BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0)
+            .corners()
+            .to_vec();
+        vertices.reverse();
+        assert_eq!(validate(&vertices).orientation, Orientation::Clockwise);
+        assert!(validate(&vertices).is_convex);
+        // a dent keeps it valid but not convex
+        vertices.insert(1, Point::new(1.0, 1.5));
+        let report = validate(&vertices);
+        assert!(report.is_valid());
+        assert!(!report.is_convex);
+    }
+    #[test]
+    fn test_problems() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let report = validate(&vertices);
+        assert_eq!(report.collinear_vertices, vec![1]);
+        assert_eq!(report.zero_length_edges, vec![2]);
+        assert!(report.duplicate_vertices.is_empty());
+        assert!(!report.is_valid());
+        // bow tie, crossing in the middle
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.0, 2.0),
+        ];
+        let report = validate(&vertices);
+        assert_eq!(report.orientation, Orientation::Degenerate);
+        assert_eq!(report.self_intersections.len(), 1);
+        assert_eq!(report.self_intersections[0].edges, (0, 2));
+        assert_eq!(report.self_intersections[0].point, Point::new(1.0, 1.0));
+        assert!(!report.is_convex);
+        // two squares pinched together at a shared corner
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 2.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        let report = validate(&vertices);
+        assert_eq!(report.duplicate_vertices, vec![6]);
+        assert!(!report.self_intersections.is_empty());
+    }
+    #[test]
+    fn test_make_valid() {
+        let mut vertices = BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0)
+            .corners()
+            .to_vec();
+        vertices.reverse();
+        vertices.insert(1, vertices[0]);
+        vertices.insert(3, Point::new(2.0, 1.0));
+        let result = make_valid(&vertices).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.polygons[0].outer.vertices.len(), 4);
+        assert_eq!(
+            orientation(&result.polygons[0].outer.vertices),
+            Orientation::CounterClockwise
+        );
+        let bow_tie = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.0, 2.0),
+        ];
+        let result = make_valid(&bow_tie).unwrap();
+        assert_eq!(result.len(), 2);
+        assert!((result.area() - 2.0).abs() < 1e-5);
+        for polygon in result.polygons.iter() {
+            assert!(validate(&polygon.outer.vertices).is_valid());
+        }
+        assert!(make_valid(&bow_tie[..2]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::algorithm::delaunay::Triangulation;
+use crate::base::{Point, Vector};
+use crate::collision::BoundingBox;
+use crate::shape::Polygon;
+
+// One counter-clockwise cell per site, clipped to the bounds. A site repeating an
+// earlier one and a site whose cell misses the bounds get an empty polygon.
+pub fn voronoi_cells(sites: &[Point], bounds: BoundingBox) -> Vec<Polygon> {
+    let mut triangulation = Triangulation::new();
+    // repeated sites come back with the vertex of the first one
+    let vertices: Vec<usize> = sites
+        .iter()
+        .map(|&site| triangulation.insert(site))
+        .collect();
+    let mut seen = vec![false; triangulation.points().len()];
+    let mut cells = Vec::with_capacity(sites.len());
+    for (&site, &vertex) in sites.iter().zip(vertices.iter()) {
+        if seen[vertex] {
+            cells.push(Polygon::default());
+            continue;
+        }
+        seen[vertex] = true;
+        let neighbors: Vec<Point> = if triangulation.triangle_count() == 0 {
+            // collinear sites leave no triangles, every other site is a neighbour
+            triangulation
+                .points()
+                .iter()
+                .copied()
+                .filter(|&point| point != site)
+                .collect()
+        } else {
+            triangulation
+                .vertex_neighbors(vertex)
+                .into_iter()
+                .map(|idx| triangulation.points()[idx])
+                .collect()
+        };
+        cells.push(cell(site, &neighbors, bounds));
+    }
+    cells
+}
+
+// Moves every site to the centroid of its cell, spreading the sites evenly over the bounds.
+pub fn lloyd_relaxation(sites: &[Point], bounds: BoundingBox, iterations: usize) -> Vec<Point> {
+    let mut sites = sites.to_vec();
+    for _ in 0..iterations {
+        let cells = voronoi_cells(&sites, bounds);
+        for (site, cell) in sites.iter_mut().zip(cells.iter()) {
+            if cell.vertices.len() >= 3 && cell.area() > 0.0 {
+                *site = cell.centroid();
+            }
+        }
+    }
+    sites
+}
+
+fn cell(site: Point, neighbors: &[Point], bounds: BoundingBox) -> Polygon {
+    let mut vertices = vec![
+        Point::new(bounds.x1(), bounds.y1()),
+        Point::new(bounds.x2(), bounds.y1()),
+        Point::new(bounds.x2(), bounds.y2()),
+        Point::new(bounds.x1(), bounds.y2()),
+    ];
+    for &neighbor in neighbors.iter() {
+        let midpoint = (site + neighbor) / 2.0;
+        vertices = clip(&vertices, midpoint, Vector::from_points(site, neighbor));
+        if vertices.is_empty() {
+            break;
+        }
+    }
+    Polygon { vertices }
+}
+
+// Sutherland-Hodgman against a single half plane, keeping the side the normal points away from
+fn clip(vertices: &[Point], origin: Point, normal: Vector) -> Vec<Point> {
+    let side = |point: Point| Vector::from_points(origin, point).dot(normal);
+    let mut result = Vec::with_capacity(vertices.len() + 1);
+    for i in 0..vertices.len() {
+        let current = vertices[i];
+        let next = vertices[(i + 1) % vertices.len()];
+        let (d_current, d_next) = (side(current), side(next));
+        if d_current <= 0.0 {
+            result.push(current);
+        }
+        if (d_current < 0.0 && d_next > 0.0) || (d_current > 0.0 && d_next < 0.0) {
+            let t = d_current / (d_current - d_next);
+            result.push(current + Vector::from_points(current, next) * t);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::voronoi::*;
+    use crate::base::Point;
+    use crate::collision::BoundingBox;
+    use crate::shape::PointLocation;
+
+    fn bounds() -> BoundingBox {
+        BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0)
+    }
+
+    #[test]
+    fn test_two_sites() {
+        let cells = voronoi_cells(&[Point::new(2.0, 5.0), Point::new(6.0, 5.0)], bounds());
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].area(), 40.0);
+        assert_eq!(cells[1].area(), 60.0);
+        assert!(cells[0].signed_area() > 0.0);
+    }
+    #[test]
+    fn test_cells_tile_bounds() {
+        let sites: Vec<Point> = (0..30)
+            .map(|i| {
+                let t = i as f32;
+                Point::new(5.0 + 4.5 * (t * 0.7).sin(), 5.0 + 4.5 * (t * 1.3).cos())
+            })
+            .collect();
+        let cells = voronoi_cells(&sites, bounds());
+        let total: f32 = cells.iter().map(|cell| cell.area()).sum();
+        assert!((total - 100.0).abs() < 1e-2, "{}", total);
+        for (site, cell) in sites.iter().zip(cells.iter()) {
+            assert!(cell.locate(*site) != PointLocation::Outside);
+            // every cell point is at least as close to its own site as to any other
+            let centroid = cell.centroid();
+            let own = centroid.distance_to(*site);
+            assert!(sites
+                .iter()
+                .all(|other| centroid.distance_to(*other) >= own - 1e-4));
+        }
+    }
+    #[test]
+    fn test_duplicates_and_outside() {
+        let sites = vec![
+            Point::new(2.0, 2.0),
+            Point::new(8.0, 8.0),
+            Point::new(2.0, 2.0),
+            Point::new(30.0, 30.0),
+        ];
+        let cells = voronoi_cells(&sites, bounds());
+        assert!(cells[2].vertices.is_empty());
+        assert!(cells[3].vertices.is_empty());
+        assert!((cells[0].area() + cells[1].area() - 100.0).abs() < 1e-3);
+    }
+    #[test]
+    fn test_lloyd_relaxation() {
+        let sites = vec![
+            Point::new(1.0, 1.0),
+            Point::new(1.5, 1.0),
+            Point::new(1.0, 1.5),
+            Point::new(1.5, 1.5),
+        ];
+        let relaxed = lloyd_relaxation(&sites, bounds(), 50);
+        // four sites settle on the centres of the quadrants
+        let mut expected = vec![
+            Point::new(2.5, 2.5),
+            Point::new(7.5, 2.5),
+            Point::new(2.5, 7.5),
+            Point::new(7.5, 7.5),
+        ];
+        for site in relaxed.iter() {
+            let idx = expected
+                .iter()
+                .position(|point| point.distance_to(*site) < 1e-2)
+                .unwrap();
+            expected.remove(idx);
+        }
+    }
+}
@@ -43,6 +43,14 @@ impl Angle {
     pub fn cos(&self) -> f64 {
         self.deg.to_radians().cos()
     }
+    // takes the shorter way round, at half a turn it goes counter-clockwise
+    pub fn lerp(&self, other: Angle, t: f64) -> Self {
+        let mut sweep = wrap(other.deg - self.deg);
+        if sweep > 180f64 {
+            sweep -= 360f64;
+        }
+        Angle::new(self.deg + sweep * t)
+    }
 }
 
 fn wrap(deg: f64) -> f64 {
@@ -96,3 +104,17 @@ impl std::fmt::Display for Angle {
         write!(f, "{}°", self.deg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::base::Angle;
+
+    #[test]
+    fn test_lerp() {
+        let from = Angle::new(350.0);
+        assert_eq!(from.lerp(Angle::new(10.0), 0.5), Angle::zero());
+        assert_eq!(Angle::new(10.0).lerp(from, 0.25), Angle::new(5.0));
+        assert_eq!(Angle::zero().lerp(Angle::pi(), 0.5), Angle::new(90.0));
+        assert_eq!(from.lerp(Angle::new(10.0), 1.0), Angle::new(10.0));
+    }
+}
@@ -27,9 +27,13 @@ impl Line {
     pub fn closest_point(&self, point: Point) -> Point {
         let ab = Vector::from_points(self.origin, self.end);
         let ap = Vector::from_points(self.origin, point);
-        let t = (ap.dx * ab.dx + ap.dy * ab.dy) / (ab.dx.powf(2.0) + ab.dy.powf(2.0));
-        let t_constr = t.min(1.0).max(0.0);
-        return self.origin + ab * t_constr;
+        let length = ab.dx.powf(2.0) + ab.dy.powf(2.0);
+        if length == 0.0 {
+            return self.origin;
+        }
+        let t = (ap.dx * ab.dx + ap.dy * ab.dy) / length;
+        let t_constr = t.clamp(0.0, 1.0);
+        self.origin + ab * t_constr
     }
     pub fn is_on_line(&self, point: Point) -> bool {
         point.distance_to(self.origin) + point.distance_to(self.end)
@@ -79,27 +83,34 @@ impl Line {
         (None, None) // no solutions
     }
     pub fn intersection_polygon(&self, polygon: &Polygon) -> (Option<Point>, Option<Point>) {
-        let poly_lines = polygon.to_lines();
-        let mut intersection_points = Vec::new();
-        for poly_line in poly_lines.iter() {
-            if let Some(point) = self.intersection(poly_line) {
-                intersection_points.push(point);
+        self.intersection_edges(polygon.edges())
+    }
+    // The two crossings nearest to the origin, nearest first. A concave outline can be
+    // crossed more often, the farther crossings are dropped.
+    pub fn intersection_edges(
+        &self,
+        edges: impl Iterator<Item = Line>,
+    ) -> (Option<Point>, Option<Point>) {
+        let mut found: [Option<Point>; 2] = [None, None];
+        let distance = |point: Option<Point>| match point {
+            Some(point) => self.origin.distance_to(point),
+            None => f32::INFINITY,
+        };
+        for edge in edges {
+            if let Some(point) = self.intersection(&edge) {
+                // an edge ending where the next one starts reports the corner twice
+                if found.contains(&Some(point)) {
+                    continue;
+                }
+                let d = distance(Some(point));
+                if d < distance(found[0]) {
+                    found = [Some(point), found[0]];
+                } else if d < distance(found[1]) {
+                    found[1] = Some(point);
+                }
             }
         }
-        intersection_points.dedup();
-        // assume polygon is convex - only two line intersections are possible
-        assert!(
-            intersection_points.len() <= 2,
-            "length = {}",
-            intersection_points.len(),
-        );
-
-        if intersection_points.len() == 2 {
-            return (Some(intersection_points[0]), Some(intersection_points[1]));
-        } else if intersection_points.len() == 1 {
-            return (Some(intersection_points[0]), None);
-        }
-        (None, None)
+        (found[0], found[1])
     }
 }
 
@@ -144,13 +155,13 @@ mod tests {
         let line = Line::new(point_a, point_b);
         // test point on line segment
         let test_point = Point::zero();
-        assert!(line.is_on_line(test_point) == true);
+        assert!(line.is_on_line(test_point));
         // test point not on line segment
         let test_point = Point::new(1.0, -1.0);
-        assert!(line.is_on_line(test_point) == false);
+        assert!(!line.is_on_line(test_point));
         // test point on line, but not on line segment
         let test_point = Point::new(2.0, 2.0);
-        assert!(line.is_on_line(test_point) == false);
+        assert!(!line.is_on_line(test_point));
     }
     #[test]
     fn test_intersection() {
@@ -158,7 +169,7 @@ mod tests {
         let line_a = Line::new(Point::new(1.0, 1.0), Point::new(-1.0, -1.0));
         let line_b = Line::new(Point::new(1.0, -1.0), Point::new(-1.0, 1.0));
         let intersection = line_a.intersection(&line_b);
-        assert!(!intersection.is_none());
+        assert!(intersection.is_some());
         assert_eq!(intersection.unwrap(), Point::zero());
 
         // test parallel line segments
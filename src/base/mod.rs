@@ -1,13 +1,17 @@
 pub use self::angle::Angle;
 pub use self::line::Line;
 pub use self::point::Point;
+pub use self::pose::Pose;
 pub use self::scale::Scale;
 pub use self::size::Size;
+pub use self::triangle::Triangle;
 pub use self::vector::Vector;
 
 mod angle;
 mod line;
 mod point;
+mod pose;
 mod scale;
 mod size;
+mod triangle;
 mod vector;
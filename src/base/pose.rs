@@ -0,0 +1,38 @@
+use crate::base::{Angle, Point, Vector};
+
+// Where a shape sits and which way it faces, without anything about its size.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Pose {
+    pub position: Point,
+    pub phi: Angle,
+}
+
+impl Pose {
+    pub fn new(position: Point, phi: Angle) -> Self {
+        Self { position, phi }
+    }
+    // t = 0 gives self and t = 1 gives other, the angle turns the short way round
+    pub fn lerp(&self, other: &Pose, t: f32) -> Self {
+        let step = Vector::from_points(self.position, other.position) * t;
+        Self {
+            position: self.position + step,
+            phi: self.phi.lerp(other.phi, t as f64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point, Pose};
+
+    #[test]
+    fn test_lerp() {
+        let a = Pose::new(Point::new(0.0, 0.0), Angle::new(300.0));
+        let b = Pose::new(Point::new(4.0, -2.0), Angle::new(20.0));
+        let half = a.lerp(&b, 0.5);
+        assert_eq!(half.position, Point::new(2.0, -1.0));
+        assert_eq!(half.phi, Angle::new(340.0));
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::base::{Point, Vector};
+use crate::shape::Polygon;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point,
+}
+
+#[allow(dead_code)]
+impl Triangle {
+    pub fn new(a: Point, b: Point, c: Point) -> Self {
+        Self { a, b, c }
+    }
+    pub fn signed_area(&self) -> f32 {
+        Vector::from_points(self.a, self.b).cross(Vector::from_points(self.a, self.c)) / 2.0
+    }
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+    pub fn centroid(&self) -> Point {
+        (self.a + self.b + self.c) / 3.0
+    }
+    pub fn contains(&self, point: Point) -> bool {
+        // inclusive of the boundary, for either winding
+        let d1 = Vector::from_points(self.a, self.b).cross(Vector::from_points(self.a, point));
+        let d2 = Vector::from_points(self.b, self.c).cross(Vector::from_points(self.b, point));
+        let d3 = Vector::from_points(self.c, self.a).cross(Vector::from_points(self.c, point));
+        let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+        !(has_negative && has_positive)
+    }
+    pub fn polygon(&self) -> Polygon {
+        Polygon {
+            vertices: vec![self.a, self.b, self.c],
+        }
+    }
+}
+
+impl std::fmt::Display for Triangle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}, {}]", self.a, self.b, self.c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Point, Triangle};
+
+    #[test]
+    fn test_area() {
+        let triangle = Triangle::new(
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 3.0),
+        );
+        assert_eq!(triangle.signed_area(), 6.0);
+        let triangle = Triangle::new(
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 3.0),
+            Point::new(4.0, 0.0),
+        );
+        assert_eq!(triangle.signed_area(), -6.0);
+        assert_eq!(triangle.area(), 6.0);
+    }
+    #[test]
+    fn test_contains() {
+        let triangle = Triangle::new(
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 3.0),
+        );
+        assert!(triangle.contains(Point::new(1.0, 1.0)));
+        assert!(triangle.contains(Point::new(2.0, 0.0)));
+        assert!(!triangle.contains(Point::new(3.0, 3.0)));
+    }
+}
@@ -60,6 +60,14 @@ impl BoundingBox {
     pub fn max_y2(&self, other: BoundingBox) -> f32 {
         self.y2().max(other.y2())
     }
+    pub fn union(&self, other: BoundingBox) -> BoundingBox {
+        let half = Size::new(
+            self.max_x2(other) - self.min_x1(other),
+            self.max_y2(other) - self.min_y1(other),
+        ) / 2.0;
+        let center = Point::new(self.min_x1(other) + half.w, self.min_y1(other) + half.h);
+        Self { center, half }
+    }
     pub fn polygon(&self) -> Polygon {
         let mut vertices = Vec::new();
         vertices.reserve(4);
@@ -69,6 +77,15 @@ impl BoundingBox {
         vertices.push(self.center + Vector::new(-self.half.w, -self.half.h));
         Polygon { vertices }
     }
+    // counter-clockwise from the corner at (x1, y1)
+    pub fn corners(&self) -> [Point; 4] {
+        [
+            Point::new(self.x1(), self.y1()),
+            Point::new(self.x2(), self.y1()),
+            Point::new(self.x2(), self.y2()),
+            Point::new(self.x1(), self.y2()),
+        ]
+    }
     pub fn is_inside(&self, point: Point) -> bool {
         point.x > self.x1() && point.x < self.x2() && point.y > self.y1() && point.y < self.y2()
     }
@@ -216,4 +233,13 @@ mod tests {
         // test overlapping with bigger box
         assert!(bb_ref.overlaps_y(BoundingBox::from_edges(x1, 0.0, x2, 6.0)) == true);
     }
+
+    #[test]
+    fn test_union() {
+        let bb_a = BoundingBox::from_edges(1.0, 1.0, 5.0, 3.0);
+        let bb_b = BoundingBox::from_edges(-2.0, 2.0, 3.0, 7.0);
+        let expected = BoundingBox::from_edges(-2.0, 1.0, 5.0, 7.0);
+        assert_eq!(bb_a.union(bb_b), expected);
+        assert_eq!(bb_b.union(bb_a), expected);
+    }
 }
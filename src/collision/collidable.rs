@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+
+use crate::algorithm::decomposition::hertel_mehlhorn;
 use crate::base::*;
 use crate::collision::*;
 use crate::shape::*;
@@ -47,7 +50,7 @@ impl Collidable for Ray {
             return None;
         }
         let time = near;
-        if time >= 0.0 && time <= 1.0 {
+        if (0.0..=1.0).contains(&time) {
             if near_x > near_y {
                 return Some(Hit::new_time(
                     self.origin + dir * time,
@@ -79,7 +82,7 @@ impl Collidable for Ray {
             let t1 = (-b + det.sqrt()) / (2.0 * a);
             let t2 = (-b - det.sqrt()) / (2.0 * a);
             let time = t1.min(t2);
-            if time >= 0.0 && time <= 1.0 {
+            if (0.0..=1.0).contains(&time) {
                 let contact = self.origin + dir * time;
                 let normal = Vector::from_points(circle.center(), contact).get_unit_vector();
                 return Some(Hit::new_time(contact, normal, -dir * (1.0 - time), time));
@@ -278,37 +281,495 @@ impl Collidable for Circle {
 
 impl Collidable for Rectangle {
     fn hit_point(&self, point: Point) -> Option<Hit> {
-        if self.polygon().is_inside(point) {
-            let closest = self.closest_point(point);
-            let delta = Vector::from_points(point, closest);
-            return Some(Hit::new(closest, delta.get_unit_vector(), delta));
-        }
-        None
+        hit_outline_point(self.cached_polygon(), point)
+    }
+    fn hit_bounding_box(&self, bounding_box: BoundingBox) -> Option<Hit> {
+        hit_outlines(self.vertices(), &bounding_box.polygon().vertices)
+    }
+    fn hit_circle(&self, circle: &Circle) -> Option<Hit> {
+        hit_outline_circle(self.cached_polygon(), circle)
+    }
+}
+
+impl Collidable for Hexagon {
+    fn hit_point(&self, point: Point) -> Option<Hit> {
+        hit_outline_point(self.cached_polygon(), point)
+    }
+    fn hit_bounding_box(&self, bounding_box: BoundingBox) -> Option<Hit> {
+        hit_outlines(self.vertices(), &bounding_box.polygon().vertices)
+    }
+    fn hit_circle(&self, circle: &Circle) -> Option<Hit> {
+        hit_outline_circle(self.cached_polygon(), circle)
+    }
+}
+
+impl Collidable for Polygon {
+    fn hit_point(&self, point: Point) -> Option<Hit> {
+        hit_outline_point(self, point)
+    }
+    fn hit_bounding_box(&self, bounding_box: BoundingBox) -> Option<Hit> {
+        hit_outlines(&self.vertices, &bounding_box.polygon().vertices)
+    }
+    fn hit_circle(&self, circle: &Circle) -> Option<Hit> {
+        hit_outline_circle(self, circle)
+    }
+}
+
+impl Collidable for Primitive {
+    fn hit_point(&self, point: Point) -> Option<Hit> {
+        self.collidable().hit_point(point)
     }
     fn hit_bounding_box(&self, bounding_box: BoundingBox) -> Option<Hit> {
-        // let vertices_inside_a = self.polygon().vertices_inside(&bounding_box.polygon());
-        let vertices_inside_b = bounding_box.polygon().vertices_inside(&self.polygon());
-        // TODO: does not work yet
-        if let Some(indices) = vertices_inside_b {
-            let mut best_distance = -std::f32::INFINITY;
-            let mut best_point = Point::zero();
-            let mut best_vertex = Point::zero();
-            for &idx in indices.iter() {
-                let vertex = self.polygon().vertices[idx];
-                let canditate = bounding_box.polygon().closest_point(vertex);
-                let distance = vertex.distance_to(canditate);
-                if distance > best_distance {
-                    best_distance = distance;
-                    best_point = canditate;
-                    best_vertex = vertex;
+        self.collidable().hit_bounding_box(bounding_box)
+    }
+    fn hit_circle(&self, circle: &Circle) -> Option<Hit> {
+        self.collidable().hit_circle(circle)
+    }
+}
+
+// the deepest hit among the children, `deepest_hit` also tells which child it was
+impl Collidable for CompoundShape {
+    fn hit_point(&self, point: Point) -> Option<Hit> {
+        self.deepest_hit(|child| child.hit_point(point))
+            .map(|(_, hit)| hit)
+    }
+    fn hit_bounding_box(&self, bounding_box: BoundingBox) -> Option<Hit> {
+        self.deepest_hit(|child| child.hit_bounding_box(bounding_box))
+            .map(|(_, hit)| hit)
+    }
+    fn hit_circle(&self, circle: &Circle) -> Option<Hit> {
+        self.deepest_hit(|child| child.hit_circle(circle))
+            .map(|(_, hit)| hit)
+    }
+}
+
+// Any two kinds, the delta moves `a` off `b`. Pairs without a `Collidable` method
+// are tested the other way round, outlines against each other by separating axes
+// and compounds child by child, keeping the deepest hit.
+pub fn collide(a: &AnyShape, b: &AnyShape) -> Option<Hit> {
+    collide_parts(Part::from(a), Part::from(b))
+}
+
+// borrowed view of either side, compound children take part without being cloned
+#[derive(Clone, Copy)]
+enum Part<'a> {
+    Point(Point),
+    BoundingBox(BoundingBox),
+    Ray(Ray),
+    Circle(&'a Circle),
+    Outline(&'a dyn Collidable, &'a [Point]),
+    Compound(&'a CompoundShape),
+}
+
+impl<'a> From<&'a AnyShape> for Part<'a> {
+    fn from(shape: &'a AnyShape) -> Self {
+        match shape.value() {
+            ShapeValue::Point(point) => Part::Point(*point),
+            ShapeValue::BoundingBox(bounds) => Part::BoundingBox(*bounds),
+            ShapeValue::Ray(ray) => Part::Ray(*ray),
+            ShapeValue::Circle(circle) => Part::Circle(circle),
+            ShapeValue::Rectangle(rectangle) => Part::Outline(rectangle, rectangle.vertices()),
+            ShapeValue::Hexagon(hexagon) => Part::Outline(hexagon, hexagon.vertices()),
+            ShapeValue::Polygon(polygon) => Part::Outline(polygon, &polygon.vertices),
+            ShapeValue::Compound(compound) => Part::Compound(compound),
+        }
+    }
+}
+
+impl<'a> From<&'a Primitive> for Part<'a> {
+    fn from(shape: &'a Primitive) -> Self {
+        match shape {
+            Primitive::Circle(circle) => Part::Circle(circle),
+            Primitive::Rectangle(rectangle) => Part::Outline(rectangle, rectangle.vertices()),
+            Primitive::Hexagon(hexagon) => Part::Outline(hexagon, hexagon.vertices()),
+            Primitive::Polygon(polygon) => Part::Outline(polygon, &polygon.vertices),
+        }
+    }
+}
+
+impl Part<'_> {
+    fn collidable(&self) -> Option<&dyn Collidable> {
+        match self {
+            Part::Point(_) => None,
+            Part::BoundingBox(bounds) => Some(bounds),
+            Part::Ray(ray) => Some(ray),
+            Part::Circle(circle) => Some(*circle),
+            Part::Outline(shape, _) => Some(*shape),
+            Part::Compound(compound) => Some(*compound),
+        }
+    }
+}
+
+fn collide_parts(a: Part, b: Part) -> Option<Hit> {
+    match (a, b) {
+        (Part::Compound(compound), _) => compound
+            .deepest_hit(|child| collide_parts(child.into(), b))
+            .map(|(_, hit)| hit),
+        (_, Part::Compound(compound)) => compound
+            .deepest_hit(|child| collide_parts(a, child.into()))
+            .map(|(_, hit)| hit),
+        (Part::Outline(_, u), Part::Outline(_, v)) => hit_outlines(u, v),
+        (Part::BoundingBox(bounds), Part::Outline(_, v)) => {
+            hit_outlines(&bounds.polygon().vertices, v)
+        }
+        (Part::Ray(ray), Part::Outline(_, v)) => hit_ray_outline(ray, v),
+        (Part::Ray(_), Part::Ray(_)) => None,
+        (_, Part::Point(point)) => a.collidable()?.hit_point(point),
+        (_, Part::BoundingBox(bounds)) => match a.collidable() {
+            Some(shape) => shape.hit_bounding_box(bounds),
+            None => collide_parts(b, a).map(reversed),
+        },
+        (_, Part::Circle(circle)) => match a.collidable() {
+            Some(shape) => shape.hit_circle(circle),
+            None => collide_parts(b, a).map(reversed),
+        },
+        (_, Part::Ray(_)) | (_, Part::Outline(..)) => collide_parts(b, a).map(reversed),
+    }
+}
+
+// the same hit seen from the other side, the contact moves to the other outline
+fn reversed(hit: Hit) -> Hit {
+    Hit::new_time(hit.contact - hit.delta, -hit.normal, -hit.delta, hit.time)
+}
+
+// first crossing along the ray, like against a bounding box
+fn hit_ray_outline(ray: Ray, vertices: &[Point]) -> Option<Hit> {
+    let dir = ray.vector();
+    let mut first: Option<(f32, Line)> = None;
+    for edge in Edges::new(vertices) {
+        let side = Vector::from_points(edge.origin, edge.end);
+        let denominator = dir.cross(side);
+        if denominator == 0.0 {
+            continue;
+        }
+        let start = Vector::from_points(ray.origin, edge.origin);
+        let time = start.cross(side) / denominator;
+        let along = start.cross(dir) / denominator;
+        if (0.0..=1.0).contains(&time)
+            && (0.0..=1.0).contains(&along)
+            && first.map_or(true, |(earliest, _)| time < earliest)
+        {
+            first = Some((time, edge));
+        }
+    }
+    let (time, edge) = first?;
+    // the side of the edge facing back along the ray
+    let mut normal = edge.to_vector().get_normal_vector().get_unit_vector();
+    if normal.dot(dir) > 0.0 {
+        normal = -normal;
+    }
+    Some(Hit::new_time(
+        ray.origin + dir * time,
+        normal,
+        -dir * (1.0 - time),
+        time,
+    ))
+}
+
+// As for the other shapes the delta moves the outline off whatever it hits, and the
+// contact lies on the other side's boundary.
+fn hit_outline_point(polygon: &Polygon, point: Point) -> Option<Hit> {
+    if polygon.locate(point) != PointLocation::Inside {
+        return None;
+    }
+    let delta = Vector::from_points(polygon.closest_point(point), point);
+    Some(Hit::new(point, delta.get_unit_vector(), delta))
+}
+
+fn hit_outline_circle(polygon: &Polygon, circle: &Circle) -> Option<Hit> {
+    if polygon.vertices.is_empty() {
+        return None;
+    }
+    let center = circle.center();
+    let offset = Vector::from_points(center, polygon.closest_point(center));
+    let distance = offset.magnitude();
+    let inside = polygon.locate(center) == PointLocation::Inside;
+    if !inside && distance >= circle.radius() {
+        return None;
+    }
+    let normal = if distance == 0.0 {
+        // centre on the outline, move inwards across that edge
+        match polygon.get_normal_vector_at(center) {
+            Some(outwards) => -outwards,
+            None => return None,
+        }
+    } else if inside {
+        -offset.get_unit_vector()
+    } else {
+        offset.get_unit_vector()
+    };
+    let depth = if inside {
+        circle.radius() + distance
+    } else {
+        circle.radius() - distance
+    };
+    Some(Hit::new(
+        center + normal * circle.radius(),
+        normal,
+        normal * depth,
+    ))
+}
+
+// Separating axes only tell convex outlines apart, so concave ones are split into
+// convex pieces first and the deepest hit between any two pieces wins.
+fn hit_outlines(a: &[Point], b: &[Point]) -> Option<Hit> {
+    let (pieces_a, pieces_b) = (convex_pieces(a), convex_pieces(b));
+    let mut deepest: Option<Hit> = None;
+    for u in pieces_a.iter() {
+        for v in pieces_b.iter() {
+            if let Some(hit) = hit_convex_outlines(u, v) {
+                if deepest.map_or(true, |best| hit.delta.magnitude() > best.delta.magnitude()) {
+                    deepest = Some(hit);
                 }
             }
-            let delta = Vector::from_points(best_point, best_vertex);
-            return Some(Hit::new(best_point, delta.get_normal_vector(), delta));
         }
-        None
     }
-    fn hit_circle(&self, circle: &Circle) -> Option<Hit> {
-        None
+    deepest
+}
+
+fn convex_pieces(vertices: &[Point]) -> Vec<Cow<'_, [Point]>> {
+    if is_convex(vertices) {
+        return vec![Cow::Borrowed(vertices)];
+    }
+    hertel_mehlhorn(vertices)
+        .into_iter()
+        .map(|piece| Cow::Owned(piece.vertices))
+        .collect()
+}
+
+// every turn goes the same way, straight runs are ignored
+fn is_convex(vertices: &[Point]) -> bool {
+    let n = vertices.len();
+    let mut sign = 0.0;
+    for i in 0..n {
+        let (a, b, c) = (vertices[i], vertices[(i + 1) % n], vertices[(i + 2) % n]);
+        let turn = Vector::from_points(a, b).cross(Vector::from_points(b, c));
+        if turn * sign < 0.0 {
+            return false;
+        }
+        if turn != 0.0 {
+            sign = turn;
+        }
+    }
+    true
+}
+
+fn hit_convex_outlines(a: &[Point], b: &[Point]) -> Option<Hit> {
+    if a.len() < 3 || b.len() < 3 {
+        return None;
+    }
+    let mut best: Option<(f32, Vector, bool)> = None;
+    for (vertices, from_a) in [(a, true), (b, false)] {
+        for edge in Edges::new(vertices) {
+            let direction = edge.to_vector();
+            if direction.magnitude() == 0.0 {
+                continue;
+            }
+            let axis = direction.get_normal_vector().get_unit_vector();
+            let (min_a, max_a) = project(a, axis);
+            let (min_b, max_b) = project(b, axis);
+            if max_a <= min_b || max_b <= min_a {
+                return None;
+            }
+            // either way along the axis, whichever is shorter
+            let (depth, normal) = if max_b - min_a < max_a - min_b {
+                (max_b - min_a, axis)
+            } else {
+                (max_a - min_b, -axis)
+            };
+            if best.map_or(true, |(smallest, _, _)| depth < smallest) {
+                best = Some((depth, normal, from_a));
+            }
+        }
+    }
+    let (depth, normal, from_a) = best?;
+    let delta = normal * depth;
+    let extreme = |vertices: &[Point], sign: f32| {
+        vertices
+            .iter()
+            .copied()
+            .max_by(|&p, &q| {
+                let (dp, dq) = (p.to_vector().dot(normal), q.to_vector().dot(normal));
+                (sign * dp).partial_cmp(&(sign * dq)).unwrap()
+            })
+            .unwrap()
+    };
+    // a face of `a` meets the deepest vertex of `b`, or the other way round
+    let contact = if from_a {
+        extreme(b, 1.0)
+    } else {
+        extreme(a, -1.0) + delta
+    };
+    Some(Hit::new(contact, normal, delta))
+}
+
+fn project(vertices: &[Point], axis: Vector) -> (f32, f32) {
+    vertices
+        .iter()
+        .map(|vertex| vertex.to_vector().dot(axis))
+        .fold((f32::INFINITY, -f32::INFINITY), |(min, max), d| {
+            (min.min(d), max.max(d))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point, Size, Vector};
+    use crate::collision::{collide, BoundingBox, Collidable, Ray};
+    use crate::shape::{
+        AnyShape, Circle, CompoundChild, CompoundShape, Hexagon, Polygon, Rectangle,
+    };
+
+    #[test]
+    fn test_outline_point() {
+        let rectangle = Rectangle::new(Point::zero(), Size::new(4.0, 2.0), Angle::zero());
+        // the delta moves the rectangle off the point, like for a bounding box
+        let hit = rectangle.hit_point(Point::new(-1.5, 0.0)).unwrap();
+        let bounds = BoundingBox::from_edges(-2.0, -1.0, 2.0, 1.0);
+        assert_eq!(Some(hit), bounds.hit_point(Point::new(-1.5, 0.0)));
+        assert_eq!(hit.delta, Vector::new(0.5, 0.0));
+        assert!(rectangle.hit_point(Point::new(3.0, 0.0)).is_none());
+        // the notch of a concave polygon is outside
+        let notched = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(2.0, 1.0),
+            Point::new(0.0, 4.0),
+        ]);
+        assert!(notched.hit_point(Point::new(2.0, 3.0)).is_none());
+        assert!(notched.hit_point(Point::new(2.0, 0.5)).is_some());
+    }
+    #[test]
+    fn test_outline_circle() {
+        let hexagon = Hexagon::new(Point::zero(), 2.0, Angle::zero());
+        let apothem = 3f32.sqrt();
+        let hit = hexagon
+            .hit_circle(&Circle::new(Point::new(0.0, 2.0), 0.5))
+            .unwrap();
+        assert_eq!(hit.normal, Vector::new(0.0, -1.0));
+        assert_eq!(hit.delta, Vector::new(0.0, -(apothem - 1.5)));
+        assert_eq!(hit.contact, Point::new(0.0, 1.5));
+        // centre inside, pushed all the way across the nearest edge
+        let hit = hexagon
+            .hit_circle(&Circle::new(Point::new(0.0, 1.0), 0.5))
+            .unwrap();
+        assert_eq!(hit.delta, Vector::new(0.0, -(apothem - 1.0 + 0.5)));
+        assert!(hexagon
+            .hit_circle(&Circle::new(Point::new(0.0, 3.0), 0.5))
+            .is_none());
+        // agrees with the box when the rectangle is axis aligned
+        let rectangle = Rectangle::new(Point::zero(), Size::new(4.0, 2.0), Angle::zero());
+        let circle = Circle::new(Point::new(2.5, 0.2), 1.0);
+        let bounds = BoundingBox::from_edges(-2.0, -1.0, 2.0, 1.0);
+        let hit = rectangle.hit_circle(&circle).unwrap();
+        assert_eq!(hit.delta, bounds.hit_circle(&circle).unwrap().delta);
+    }
+    #[test]
+    fn test_outline_bounding_box() {
+        let rectangle = Rectangle::new(Point::zero(), Size::new(4.0, 2.0), Angle::zero());
+        let other = BoundingBox::from_edges(1.5, -3.0, 5.0, 3.0);
+        let hit = rectangle.hit_bounding_box(other).unwrap();
+        let expected = BoundingBox::from_edges(-2.0, -1.0, 2.0, 1.0)
+            .hit_bounding_box(other)
+            .unwrap();
+        assert_eq!(hit.delta, expected.delta);
+        assert_eq!(hit.normal, Vector::new(-1.0, 0.0));
+        // turned by 45 degrees the corner pokes into the box
+        let diamond = Rectangle::new(Point::zero(), Size::new(2.0, 2.0), Angle::new(45.0));
+        let hit = diamond
+            .hit_bounding_box(BoundingBox::from_edges(1.0, -1.0, 3.0, 1.0))
+            .unwrap();
+        assert_eq!(hit.delta, Vector::new(-(2f32.sqrt() - 1.0), 0.0));
+        assert_eq!(hit.contact, Point::new(1.0, 0.0));
+        assert!(diamond
+            .hit_bounding_box(BoundingBox::from_edges(1.5, -1.0, 3.0, 1.0))
+            .is_none());
+    }
+    #[test]
+    fn test_collide() {
+        let square: AnyShape =
+            Rectangle::new(Point::zero(), Size::new(2.0, 2.0), Angle::zero()).into();
+        let point: AnyShape = Point::new(0.5, 0.0).into();
+        // either way round, the deltas point in opposite directions
+        let hit = collide(&square, &point).unwrap();
+        assert_eq!(hit.delta, Vector::new(-0.5, 0.0));
+        let hit = collide(&point, &square).unwrap();
+        assert_eq!(hit.delta, Vector::new(0.5, 0.0));
+        assert!(collide(&point, &point).is_none());
+        // outlines against each other by separating axes
+        let hexagon: AnyShape = Hexagon::new(Point::new(2.5, 0.0), 2.0, Angle::zero()).into();
+        let hit = collide(&square, &hexagon).unwrap();
+        assert_eq!(hit.delta, Vector::new(-0.5, 0.0));
+        let bounds: AnyShape = BoundingBox::from_edges(0.5, -3.0, 3.0, 3.0).into();
+        assert_eq!(
+            collide(&bounds, &square).unwrap().delta,
+            Vector::new(0.5, 0.0)
+        );
+        let circle: AnyShape = Circle::new(Point::new(0.0, 1.5), 1.0).into();
+        assert_eq!(
+            collide(&circle, &square).unwrap().delta,
+            Vector::new(0.0, 0.5)
+        );
+        // a ray stops at the first edge it crosses
+        let ray: AnyShape = Ray::new(Point::new(-3.0, 0.0), Angle::zero(), 4.0).into();
+        let hit = collide(&ray, &square).unwrap();
+        assert_eq!(hit.contact, Point::new(-1.0, 0.0));
+        assert_eq!(hit.normal, Vector::new(-1.0, 0.0));
+        assert_eq!(hit.time, 0.5);
+        assert!(collide(&ray, &ray).is_none());
+        let short: AnyShape = Ray::new(Point::new(-3.0, 0.0), Angle::zero(), 1.0).into();
+        assert!(collide(&short, &square).is_none());
+    }
+    #[test]
+    fn test_collide_concave() {
+        let corner = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 4.0),
+            Point::new(0.0, 4.0),
+        ]);
+        // the box sits in the notch, inside the hull but clear of both arms
+        let notch = BoundingBox::from_edges(1.5, 1.5, 3.5, 3.5);
+        assert!(corner.hit_bounding_box(notch).is_none());
+        let shape: AnyShape = corner.clone().into();
+        let square: AnyShape =
+            Rectangle::new(Point::new(2.5, 2.5), Size::new(2.0, 2.0), Angle::zero()).into();
+        assert!(collide(&shape, &square).is_none());
+        assert!(collide(&square, &shape).is_none());
+        // resting on the lower arm it is pushed off the shorter way
+        let resting = BoundingBox::from_edges(2.0, 0.5, 3.5, 2.0);
+        let hit = corner.hit_bounding_box(resting).unwrap();
+        assert_eq!(hit.delta, Vector::new(0.0, -0.5));
+        let compound: AnyShape = CompoundShape::new(
+            Point::zero(),
+            Angle::zero(),
+            vec![CompoundChild::new(corner, Vector::zero(), Angle::zero())],
+        )
+        .into();
+        assert!(collide(&compound, &square).is_none());
+    }
+    #[test]
+    fn test_collide_compound() {
+        let wheel = Circle::new(Point::zero(), 1.0);
+        let axle: AnyShape = CompoundShape::new(
+            Point::zero(),
+            Angle::zero(),
+            vec![
+                CompoundChild::new(wheel.clone(), Vector::new(-3.0, 0.0), Angle::zero()),
+                CompoundChild::new(wheel, Vector::new(3.0, 0.0), Angle::zero()),
+            ],
+        )
+        .into();
+        let wall: AnyShape =
+            Rectangle::new(Point::new(4.5, 0.0), Size::new(2.0, 4.0), Angle::zero()).into();
+        let hit = collide(&axle, &wall).unwrap();
+        assert_eq!(hit.delta, Vector::new(-0.5, 0.0));
+        assert_eq!(collide(&wall, &axle).unwrap().delta, Vector::new(0.5, 0.0));
+        let gap: AnyShape = Point::new(0.0, 0.0).into();
+        assert!(collide(&axle, &gap).is_none());
+        assert!(collide(&axle, &axle).is_some());
     }
 }
@@ -2,17 +2,24 @@ use crate::base::{Point, Vector};
 use crate::shape::*;
 
 pub fn are_close(shape_a: &dyn Shape, shape_b: &dyn Shape, margin: f32) -> bool {
-    shape_a.center().distance_to(shape_b.center()) + margin
-        < shape_a.enclosing_radius() + shape_b.enclosing_radius()
+    // circles around the centers also enclose the shapes, when they are apart the
+    // shapes are too and the minimal circles are not needed
+    if shape_a.center().distance_to(shape_b.center()) + margin
+        >= shape_a.enclosing_radius() + shape_b.enclosing_radius()
+    {
+        return false;
+    }
+    let circle_a = shape_a.enclosing_circle();
+    let circle_b = shape_b.enclosing_circle();
+    circle_a.center().distance_to(circle_b.center()) + margin
+        < circle_a.radius() + circle_b.radius()
 }
 pub fn distance_closest_points(shape_a: &dyn Shape, shape_b: &dyn Shape) -> (f32, Point, Point) {
     // TODO: optimize
-    let poly_a = shape_a.polygon();
-    let poly_b = shape_b.polygon();
-    let mut best = std::f32::INFINITY;
+    let mut best = f32::INFINITY;
     let mut best_point_a = Point::zero();
     let mut best_point_b = Point::zero();
-    for &point in poly_a.vertices.iter() {
+    for &point in shape_a.vertices().iter() {
         let closest = shape_b.closest_point(point);
         let candidate = closest.distance_to(point);
         if candidate < best {
@@ -21,7 +28,7 @@ pub fn distance_closest_points(shape_a: &dyn Shape, shape_b: &dyn Shape) -> (f32
             best_point_b = closest;
         };
     }
-    for &point in poly_b.vertices.iter() {
+    for &point in shape_b.vertices().iter() {
         let closest = shape_a.closest_point(point);
         let candidate = closest.distance_to(point);
         if candidate < best {
@@ -48,3 +55,24 @@ pub fn ball_bounce(ball: &dyn Shape, velocity: Vector, object: &dyn Shape) -> Op
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::base::Point;
+    use crate::collision::detection;
+    use crate::shape::{Circle, Polygon};
+
+    #[test]
+    fn test_are_close() {
+        // long thin triangle, far from its bounding box center on the left
+        let triangle = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(20.0, 0.0),
+            Point::new(0.0, 1.0),
+        ]);
+        let circle = Circle::new(Point::new(-3.0, 10.0), 2.0);
+        assert!(!detection::are_close(&triangle, &circle, 0.0));
+        let circle = Circle::new(Point::new(10.0, 2.0), 2.0);
+        assert!(detection::are_close(&triangle, &circle, 0.0));
+    }
+}
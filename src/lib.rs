@@ -1,3 +1,4 @@
+pub mod algorithm;
 pub mod base;
 pub mod collision;
 pub mod shape;
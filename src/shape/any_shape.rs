@@ -0,0 +1,402 @@
+use crate::algorithm::calipers::minimum_area_rectangle;
+use crate::algorithm::enclosing::minimum_enclosing_circle;
+use crate::base::{Angle, Point, Pose, Size, Vector};
+use crate::collision::{BoundingBox, Ray};
+use crate::shape::{shape::*, Circle, CompoundShape, Hexagon, MassProperties, Polygon, Rectangle};
+
+// One of every kind, so mixed shapes fit in a single `Vec` without boxing. Bounding
+// boxes and rays have no outline of their own, so `AnyShape` keeps one beside the
+// value for `vertices` to lend out. Only `from` and the `Shape` methods touch either,
+// which keeps the two in step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnyShape {
+    value: ShapeValue,
+    outline: Vec<Point>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShapeValue {
+    Point(Point),
+    BoundingBox(BoundingBox),
+    Ray(Ray),
+    Circle(Circle),
+    Rectangle(Rectangle),
+    Hexagon(Hexagon),
+    Polygon(Polygon),
+    Compound(CompoundShape),
+}
+
+impl AnyShape {
+    pub fn value(&self) -> &ShapeValue {
+        &self.value
+    }
+    // none for the kinds that are not shapes of their own
+    pub fn shape(&self) -> Option<&dyn Shape> {
+        match &self.value {
+            ShapeValue::Point(_) | ShapeValue::BoundingBox(_) | ShapeValue::Ray(_) => None,
+            ShapeValue::Circle(circle) => Some(circle),
+            ShapeValue::Rectangle(rectangle) => Some(rectangle),
+            ShapeValue::Hexagon(hexagon) => Some(hexagon),
+            ShapeValue::Polygon(polygon) => Some(polygon),
+            ShapeValue::Compound(compound) => Some(compound),
+        }
+    }
+    fn shape_mut(&mut self) -> Option<&mut dyn Shape> {
+        match &mut self.value {
+            ShapeValue::Point(_) | ShapeValue::BoundingBox(_) | ShapeValue::Ray(_) => None,
+            ShapeValue::Circle(circle) => Some(circle),
+            ShapeValue::Rectangle(rectangle) => Some(rectangle),
+            ShapeValue::Hexagon(hexagon) => Some(hexagon),
+            ShapeValue::Polygon(polygon) => Some(polygon),
+            ShapeValue::Compound(compound) => Some(compound),
+        }
+    }
+    fn update_outline(&mut self) {
+        self.outline = match self.value {
+            ShapeValue::BoundingBox(bounds) => bounds.corners().to_vec(),
+            ShapeValue::Ray(ray) => vec![ray.origin, ray.origin + ray.vector()],
+            _ => Vec::new(),
+        };
+    }
+    fn outline(&self) -> Polygon {
+        Polygon::new(self.vertices())
+    }
+}
+
+impl From<ShapeValue> for AnyShape {
+    fn from(value: ShapeValue) -> Self {
+        let mut shape = AnyShape {
+            value,
+            outline: Vec::new(),
+        };
+        shape.update_outline();
+        shape
+    }
+}
+
+impl From<Point> for AnyShape {
+    fn from(point: Point) -> Self {
+        ShapeValue::Point(point).into()
+    }
+}
+
+impl From<BoundingBox> for AnyShape {
+    fn from(bounds: BoundingBox) -> Self {
+        ShapeValue::BoundingBox(bounds).into()
+    }
+}
+
+impl From<Ray> for AnyShape {
+    fn from(ray: Ray) -> Self {
+        ShapeValue::Ray(ray).into()
+    }
+}
+
+impl From<Circle> for AnyShape {
+    fn from(circle: Circle) -> Self {
+        ShapeValue::Circle(circle).into()
+    }
+}
+
+impl From<Rectangle> for AnyShape {
+    fn from(rectangle: Rectangle) -> Self {
+        ShapeValue::Rectangle(rectangle).into()
+    }
+}
+
+impl From<Hexagon> for AnyShape {
+    fn from(hexagon: Hexagon) -> Self {
+        ShapeValue::Hexagon(hexagon).into()
+    }
+}
+
+impl From<Polygon> for AnyShape {
+    fn from(polygon: Polygon) -> Self {
+        ShapeValue::Polygon(polygon).into()
+    }
+}
+
+impl From<CompoundShape> for AnyShape {
+    fn from(compound: CompoundShape) -> Self {
+        ShapeValue::Compound(compound).into()
+    }
+}
+
+// A point is placed by itself and a ray by its origin, both turn a ray about its
+// origin. Bounding boxes stay axis aligned, turning only moves their centre.
+impl Shape for AnyShape {
+    fn get_type(&self) -> ShapeType {
+        match &self.value {
+            ShapeValue::Point(_) => ShapeType::Point,
+            ShapeValue::BoundingBox(_) => ShapeType::BoundingBox,
+            ShapeValue::Ray(_) => ShapeType::Ray,
+            _ => self.shape().unwrap().get_type(),
+        }
+    }
+    fn center(&self) -> Point {
+        match &self.value {
+            ShapeValue::Point(point) => *point,
+            ShapeValue::BoundingBox(bounds) => bounds.center,
+            ShapeValue::Ray(ray) => ray.origin,
+            _ => self.shape().unwrap().center(),
+        }
+    }
+    fn enclosing_radius(&self) -> f32 {
+        match &self.value {
+            ShapeValue::Point(_) => 0.0,
+            ShapeValue::BoundingBox(bounds) => bounds.half.w.hypot(bounds.half.h),
+            ShapeValue::Ray(ray) => ray.length,
+            _ => self.shape().unwrap().enclosing_radius(),
+        }
+    }
+    fn translate(&mut self, vector: Vector) {
+        match &mut self.value {
+            ShapeValue::Point(point) => *point = *point + vector,
+            ShapeValue::BoundingBox(bounds) => bounds.center = bounds.center + vector,
+            ShapeValue::Ray(ray) => ray.origin = ray.origin + vector,
+            _ => self.shape_mut().unwrap().translate(vector),
+        }
+        self.update_outline();
+    }
+    fn move_to(&mut self, point: Point) {
+        match &mut self.value {
+            ShapeValue::Point(position) => *position = point,
+            ShapeValue::BoundingBox(bounds) => bounds.center = point,
+            ShapeValue::Ray(ray) => ray.origin = point,
+            _ => self.shape_mut().unwrap().move_to(point),
+        }
+        self.update_outline();
+    }
+    fn rotate(&mut self, theta: Angle) {
+        match &mut self.value {
+            ShapeValue::Point(_) | ShapeValue::BoundingBox(_) => (),
+            ShapeValue::Ray(ray) => ray.direction = ray.direction + theta,
+            _ => self.shape_mut().unwrap().rotate(theta),
+        }
+        self.update_outline();
+    }
+    fn rotate_to(&mut self, phi: Angle) {
+        match &mut self.value {
+            ShapeValue::Point(_) | ShapeValue::BoundingBox(_) => (),
+            ShapeValue::Ray(ray) => ray.direction = phi,
+            _ => self.shape_mut().unwrap().rotate_to(phi),
+        }
+        self.update_outline();
+    }
+    fn pose(&self) -> Pose {
+        match &self.value {
+            ShapeValue::Ray(ray) => Pose::new(ray.origin, ray.direction),
+            ShapeValue::Point(_) | ShapeValue::BoundingBox(_) => {
+                Pose::new(self.center(), Angle::zero())
+            }
+            _ => self.shape().unwrap().pose(),
+        }
+    }
+    fn rotate_about(&mut self, point: Point, theta: Angle) {
+        match &mut self.value {
+            ShapeValue::Point(position) => position.rotate_about(point, theta),
+            ShapeValue::BoundingBox(bounds) => bounds.center.rotate_about(point, theta),
+            ShapeValue::Ray(ray) => {
+                ray.origin.rotate_about(point, theta);
+                ray.direction = ray.direction + theta;
+            }
+            _ => self.shape_mut().unwrap().rotate_about(point, theta),
+        }
+        self.update_outline();
+    }
+    fn vertices(&self) -> &[Point] {
+        match &self.value {
+            ShapeValue::Point(point) => std::slice::from_ref(point),
+            ShapeValue::BoundingBox(_) | ShapeValue::Ray(_) => &self.outline,
+            _ => self.shape().unwrap().vertices(),
+        }
+    }
+    fn bounding_box(&self) -> BoundingBox {
+        match &self.value {
+            ShapeValue::Point(point) => BoundingBox::new(*point, Size::new(0.0, 0.0)),
+            ShapeValue::BoundingBox(bounds) => *bounds,
+            ShapeValue::Ray(ray) => {
+                let half = ray.vector() / 2.0;
+                BoundingBox::new(ray.origin + half, Size::new(half.dx.abs(), half.dy.abs()))
+            }
+            _ => self.shape().unwrap().bounding_box(),
+        }
+    }
+    fn closest_point(&self, point: Point) -> Point {
+        match &self.value {
+            ShapeValue::Point(position) => *position,
+            ShapeValue::BoundingBox(_) | ShapeValue::Ray(_) => self.outline().closest_point(point),
+            _ => self.shape().unwrap().closest_point(point),
+        }
+    }
+    fn signed_distance(&self, point: Point) -> f32 {
+        match self.shape() {
+            Some(shape) => shape.signed_distance(point),
+            None => signed_distance_to_outline(self.vertices(), point),
+        }
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        match self.shape() {
+            Some(shape) => shape.gradient(point),
+            None => {
+                let from = self.closest_point(point);
+                if from == point {
+                    return Vector::zero();
+                }
+                // inside a box the closest point lies ahead, not behind
+                let away = Vector::from_points(from, point).get_unit_vector();
+                if self.signed_distance(point) < 0.0 {
+                    -away
+                } else {
+                    away
+                }
+            }
+        }
+    }
+    fn area(&self) -> f32 {
+        match self.shape() {
+            Some(shape) => shape.area(),
+            None => self.outline().area(),
+        }
+    }
+    fn perimeter(&self) -> f32 {
+        match self.shape() {
+            Some(shape) => shape.perimeter(),
+            None => self.outline().perimeter(),
+        }
+    }
+    fn centroid(&self) -> Point {
+        match self.shape() {
+            Some(shape) => shape.centroid(),
+            None => self.outline().centroid(),
+        }
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        match self.shape() {
+            Some(shape) => shape.mass_properties(density),
+            None => self.outline().mass_properties(density),
+        }
+    }
+    fn enclosing_circle(&self) -> Circle {
+        match self.shape() {
+            Some(shape) => shape.enclosing_circle(),
+            None => minimum_enclosing_circle(self.vertices()),
+        }
+    }
+    fn oriented_bounding_box(&self) -> Rectangle {
+        match self.shape() {
+            Some(shape) => shape.oriented_bounding_box(),
+            None => minimum_area_rectangle(self.vertices()),
+        }
+    }
+    fn contact_point(&self, origin: Point, direction: Vector) -> Option<Point> {
+        match self.shape() {
+            Some(shape) => shape.contact_point(origin, direction),
+            None => {
+                let reach = origin.distance_to(self.center()) + self.enclosing_radius();
+                contact_on_outline(self.vertices(), origin, direction, reach)
+            }
+        }
+    }
+    fn get_normal_vector_at(&self, point: Point) -> Option<Vector> {
+        match self.shape() {
+            Some(shape) => shape.get_normal_vector_at(point),
+            None => normal_on_outline(self.vertices(), point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point, Size, Vector};
+    use crate::collision::{BoundingBox, Ray};
+    use crate::shape::{shape::*, AnyShape, Circle, Rectangle, ShapeValue};
+
+    #[test]
+    fn test_mixed() {
+        let mut shapes: Vec<AnyShape> = vec![
+            Point::new(1.0, 1.0).into(),
+            BoundingBox::from_edges(0.0, 0.0, 2.0, 1.0).into(),
+            Ray::new(Point::zero(), Angle::zero(), 3.0).into(),
+            Circle::new(Point::zero(), 1.0).into(),
+            Rectangle::new(Point::zero(), Size::new(2.0, 2.0), Angle::zero()).into(),
+        ];
+        let kinds: Vec<ShapeType> = shapes.iter().map(|shape| shape.get_type()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ShapeType::Point,
+                ShapeType::BoundingBox,
+                ShapeType::Ray,
+                ShapeType::Circle,
+                ShapeType::Rectangle
+            ]
+        );
+        for shape in shapes.iter_mut() {
+            shape.translate(Vector::new(1.0, 0.0));
+        }
+        assert_eq!(shapes[0].center(), Point::new(2.0, 1.0));
+        // the outlines follow the values
+        assert_eq!(
+            shapes[1].bounding_box(),
+            BoundingBox::from_edges(1.0, 0.0, 3.0, 1.0)
+        );
+        assert_eq!(shapes[1].vertices()[0], Point::new(1.0, 0.0));
+        assert_eq!(shapes[2].vertices()[1], Point::new(4.0, 0.0));
+        assert_eq!(shapes[3].center(), Point::new(1.0, 0.0));
+        assert_eq!(shapes[4].area(), 4.0);
+    }
+    #[test]
+    fn test_value_kinds() {
+        let mut ray = AnyShape::from(Ray::new(Point::zero(), Angle::zero(), 2.0));
+        ray.rotate(Angle::new(90.0));
+        assert_eq!(ray.vertices()[1], Point::new(0.0, 2.0));
+        assert_eq!(ray.bounding_box().height(), 2.0);
+        assert_eq!(
+            ray.closest_point(Point::new(1.0, 1.0)),
+            Point::new(0.0, 1.0)
+        );
+        assert_eq!(ray.area(), 0.0);
+        let bounds = AnyShape::from(BoundingBox::from_edges(0.0, 0.0, 4.0, 2.0));
+        assert_eq!(bounds.area(), 8.0);
+        assert_eq!(bounds.centroid(), Point::new(2.0, 1.0));
+        assert_eq!(
+            bounds.closest_point(Point::new(1.0, 5.0)),
+            Point::new(1.0, 2.0)
+        );
+        assert_eq!(
+            bounds.contact_point(Point::new(-3.0, 1.0), Vector::new(1.0, 0.0)),
+            Some(Point::new(0.0, 1.0))
+        );
+        assert_eq!(
+            bounds.get_normal_vector_at(Point::new(4.0, 1.0)),
+            Some(Vector::new(1.0, 0.0))
+        );
+        let point = AnyShape::from(Point::new(1.0, 2.0));
+        assert_eq!(point.vertices(), &[Point::new(1.0, 2.0)]);
+        assert_eq!(point.bounding_box().width(), 0.0);
+        assert_eq!(point.get_normal_vector_at(Point::new(1.0, 2.0)), None);
+        assert_eq!(point.enclosing_circle().radius(), 0.0);
+    }
+    #[test]
+    fn test_value() {
+        let ray = Ray::new(Point::zero(), Angle::zero(), 2.0);
+        let mut shape = AnyShape::from(ray);
+        assert_eq!(shape.value(), &ShapeValue::Ray(ray));
+        shape.translate(Vector::new(1.0, 0.0));
+        match shape.value() {
+            ShapeValue::Ray(moved) => assert_eq!(moved.origin, Point::new(1.0, 0.0)),
+            _ => panic!("expected a ray"),
+        }
+    }
+    #[test]
+    fn test_equality() {
+        let bounds = BoundingBox::from_edges(0.0, 0.0, 4.0, 2.0);
+        let mut moved = AnyShape::from(bounds);
+        moved.translate(Vector::new(1.0, 0.0));
+        moved.translate(Vector::new(-1.0, 0.0));
+        assert_eq!(moved, AnyShape::from(bounds));
+        moved.move_to(Point::new(5.0, 5.0));
+        assert_ne!(moved, AnyShape::from(bounds));
+    }
+}
@@ -1,60 +1,71 @@
-use crate::base::{Angle, Line, Point, Vector};
+use crate::base::{Angle, Line, Point, Size, Vector};
 use crate::collision::BoundingBox;
 pub use crate::shape::shape::*;
-use crate::shape::Polygon;
-use std::cell::RefCell;
+use crate::shape::{MassProperties, Polygon, Tessellation};
+use std::sync::OnceLock;
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default)]
 pub struct Circle {
     center: Point,
     radius: f32,
-    _polygon: RefCell<Option<Polygon>>,
-    _bounding_box: RefCell<Option<BoundingBox>>,
+    tessellation: Tessellation,
+    // built with `tessellation`, reset whenever it changes
+    _polygon: OnceLock<Polygon>,
+    _bounding_box: OnceLock<BoundingBox>,
 }
 
 impl Circle {
     pub fn new(center: Point, radius: f32) -> Self {
+        Self::with_tessellation(center, radius, Tessellation::default())
+    }
+    pub fn with_tessellation(center: Point, radius: f32, tessellation: Tessellation) -> Self {
         Self {
             center,
             radius,
-            _polygon: RefCell::new(None),
-            _bounding_box: RefCell::new(None),
+            tessellation,
+            _polygon: OnceLock::new(),
+            _bounding_box: OnceLock::new(),
         }
     }
     pub fn radius(&self) -> f32 {
         self.radius
     }
+    pub fn tessellation(&self) -> Tessellation {
+        self.tessellation
+    }
+    pub fn set_tessellation(&mut self, tessellation: Tessellation) {
+        if tessellation != self.tessellation {
+            self.tessellation = tessellation;
+            self._polygon = OnceLock::new();
+        }
+    }
+    // only the circle's own options are cached, others are built on every call
+    pub fn polygon_with(&self, tessellation: Tessellation) -> Polygon {
+        if tessellation == self.tessellation {
+            return self.polygon();
+        }
+        self.create_polygon(tessellation)
+    }
     pub fn is_inslide(&self, point: Point) -> bool {
         self.center.distance_to(point) < self.radius
     }
-    fn invalidate(&self) {
-        *self._polygon.borrow_mut() = None;
-        *self._bounding_box.borrow_mut() = None;
-    }
-    fn create_polygon(&self) {
-        // determine number of polygon vertices from radius
-        let n_vertices = 4 + (4.0 * self.radius.sqrt().floor()) as usize;
-        let mut vertices = Vec::new();
-        vertices.reserve(n_vertices);
-        let angle_step = (360.0 / n_vertices as f32).to_radians();
-        for i in 0..n_vertices {
-            vertices.push(
-                self.center
-                    + Vector::new(
-                        self.radius * ((i as f32) * angle_step).cos(),
-                        self.radius * ((i as f32) * angle_step).sin(),
-                    ),
-            )
+    fn invalidate(&mut self) {
+        self._polygon = OnceLock::new();
+        self._bounding_box = OnceLock::new();
+    }
+    fn create_polygon(&self, tessellation: Tessellation) -> Polygon {
+        Polygon {
+            vertices: tessellation.circle(self.center, self.radius),
         }
-        *self._polygon.borrow_mut() = Some(Polygon { vertices });
     }
-    fn create_bounding_box(&self) {
-        *self._bounding_box.borrow_mut() = Some(BoundingBox::from_edges(
-            self.center().x - self.radius,
-            self.center().y + self.radius,
-            self.center().x - self.radius,
-            self.center().y + self.radius,
-        ));
+}
+
+// the caches are left out, they only hold what the other fields determine
+impl PartialEq for Circle {
+    fn eq(&self, other: &Self) -> bool {
+        self.center == other.center
+            && self.radius == other.radius
+            && self.tessellation == other.tessellation
     }
 }
 
@@ -86,22 +97,48 @@ impl Shape for Circle {
         self.center.rotate_about(point, theta);
         self.invalidate();
     }
-    fn polygon(&self) -> Polygon {
-        if *self._polygon.borrow() == None {
-            self.create_polygon();
-        }
-        (*self._polygon.borrow()).clone().unwrap()
+    fn vertices(&self) -> &[Point] {
+        &self
+            ._polygon
+            .get_or_init(|| self.create_polygon(self.tessellation))
+            .vertices
     }
     fn bounding_box(&self) -> BoundingBox {
-        if *self._bounding_box.borrow() == None {
-            self.create_bounding_box();
-        }
-        (*self._bounding_box.borrow()).clone().unwrap()
+        *self
+            ._bounding_box
+            .get_or_init(|| BoundingBox::new(self.center, Size::new(self.radius, self.radius)))
     }
     fn closest_point(&self, point: Point) -> Point {
         let v = Vector::from_points(self.center, point).get_unit_vector();
         self.center + (v * self.radius).to_point()
     }
+    // exact for the true circle, not its tessellation
+    fn signed_distance(&self, point: Point) -> f32 {
+        point.distance_to(self.center) - self.radius
+    }
+    fn gradient(&self, point: Point) -> Vector {
+        if point == self.center {
+            return Vector::zero();
+        }
+        Vector::from_points(self.center, point).get_unit_vector()
+    }
+    fn area(&self) -> f32 {
+        std::f32::consts::PI * self.radius.powf(2.0)
+    }
+    fn perimeter(&self) -> f32 {
+        2.0 * std::f32::consts::PI * self.radius
+    }
+    fn centroid(&self) -> Point {
+        self.center
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        let mass = density * self.area();
+        let moment = mass * self.radius.powf(2.0) / 4.0;
+        MassProperties::new(mass, self.center, moment, moment, 0.0)
+    }
+    fn enclosing_circle(&self) -> Circle {
+        Circle::new(self.center, self.radius)
+    }
     fn contact_point(&self, origin: Point, direction: Vector) -> Option<Point> {
         let extended =
             direction.get_unit_vector() * (origin.distance_to(self.center) + self.radius);
@@ -131,8 +168,9 @@ impl Shape for Circle {
 
 #[cfg(test)]
 mod tests {
-    use crate::base::{Point, Vector};
-    use crate::shape::{shape::Shape, Circle};
+    use crate::base::{Angle, Point, Vector};
+    use crate::collision::BoundingBox;
+    use crate::shape::{shape::Shape, Circle, Hexagon, Rectangle, Tessellation};
 
     #[test]
     fn test_translate() {
@@ -175,6 +213,48 @@ mod tests {
         );
     }
     #[test]
+    fn test_tessellation() {
+        let mut circle = Circle::new(Point::new(0.0, 0.0), 100.0);
+        let fine = circle.polygon().vertices.len();
+        let coarse = Tessellation::new(5.0, 4, 64, Angle::zero());
+        assert!(circle.polygon_with(coarse).vertices.len() < fine);
+        // asking again with the default options does not reuse the coarse polygon
+        assert_eq!(circle.polygon().vertices.len(), fine);
+        circle.set_tessellation(coarse);
+        assert_eq!(circle.polygon(), circle.polygon_with(coarse));
+        let small = Circle::new(Point::new(0.0, 0.0), 0.5);
+        assert_eq!(small.polygon().vertices.len(), 8);
+    }
+    #[test]
+    fn test_bounding_box() {
+        let mut circle = Circle::new(Point::new(1.0, 2.0), 3.0);
+        assert_eq!(
+            circle.bounding_box(),
+            BoundingBox::from_edges(-2.0, -1.0, 4.0, 5.0)
+        );
+        circle.translate(Vector::new(1.0, 0.0));
+        assert_eq!(circle.bounding_box().x2(), 5.0);
+        let point = Circle::new(Point::new(1.0, 2.0), 0.0);
+        assert_eq!(point.bounding_box().width(), 0.0);
+    }
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Circle>();
+        assert_send_sync::<Rectangle>();
+        assert_send_sync::<Hexagon>();
+        // the first thread to ask fills the cache, the others see the same polygon
+        let circle = Circle::new(Point::new(0.0, 0.0), 10.0);
+        let polygons: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| circle.polygon())).collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert!(polygons.iter().all(|polygon| *polygon == circle.polygon()));
+    }
+    #[test]
     fn test_closest_point() {
         let circle = Circle::new(Point::new(0.0, 0.0), 10.0);
         let point = Point::new(15.0, -15.0);
@@ -182,4 +262,20 @@ mod tests {
         let expected = Point::new(10.0 / 2f32.sqrt(), -10.0 / 2f32.sqrt());
         assert_eq!(result, expected);
     }
+    #[test]
+    fn test_area_perimeter_centroid() {
+        let circle = Circle::new(Point::new(3.0, -1.0), 2.0);
+        assert_eq!(circle.area(), 4.0 * std::f32::consts::PI);
+        assert_eq!(circle.perimeter(), 4.0 * std::f32::consts::PI);
+        assert_eq!(circle.centroid(), Point::new(3.0, -1.0));
+    }
+    #[test]
+    fn test_mass_properties() {
+        let circle = Circle::new(Point::new(1.0, 2.0), 2.0);
+        let props = circle.mass_properties(3.0);
+        let mass = 3.0 * 4.0 * std::f32::consts::PI;
+        assert_eq!(props.mass, mass);
+        assert_eq!(props.inertia(), mass * 4.0 / 2.0);
+        assert_eq!(props.centroid, Point::new(1.0, 2.0));
+    }
 }
@@ -0,0 +1,355 @@
+use crate::algorithm::hull::convex_hull;
+use crate::base::{Angle, Point, Pose, Size, Vector};
+use crate::collision::{BoundingBox, Collidable, Hit};
+use crate::shape::{shape::*, Circle, Hexagon, MassProperties, Polygon, Rectangle};
+use std::sync::OnceLock;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Primitive {
+    Circle(Circle),
+    Rectangle(Rectangle),
+    Hexagon(Hexagon),
+    Polygon(Polygon),
+}
+
+impl Primitive {
+    pub fn shape(&self) -> &dyn Shape {
+        match self {
+            Primitive::Circle(circle) => circle,
+            Primitive::Rectangle(rectangle) => rectangle,
+            Primitive::Hexagon(hexagon) => hexagon,
+            Primitive::Polygon(polygon) => polygon,
+        }
+    }
+    pub fn shape_mut(&mut self) -> &mut dyn Shape {
+        match self {
+            Primitive::Circle(circle) => circle,
+            Primitive::Rectangle(rectangle) => rectangle,
+            Primitive::Hexagon(hexagon) => hexagon,
+            Primitive::Polygon(polygon) => polygon,
+        }
+    }
+    pub fn collidable(&self) -> &dyn Collidable {
+        match self {
+            Primitive::Circle(circle) => circle,
+            Primitive::Rectangle(rectangle) => rectangle,
+            Primitive::Hexagon(hexagon) => hexagon,
+            Primitive::Polygon(polygon) => polygon,
+        }
+    }
+}
+
+impl From<Circle> for Primitive {
+    fn from(circle: Circle) -> Self {
+        Primitive::Circle(circle)
+    }
+}
+
+impl From<Rectangle> for Primitive {
+    fn from(rectangle: Rectangle) -> Self {
+        Primitive::Rectangle(rectangle)
+    }
+}
+
+impl From<Hexagon> for Primitive {
+    fn from(hexagon: Hexagon) -> Self {
+        Primitive::Hexagon(hexagon)
+    }
+}
+
+impl From<Polygon> for Primitive {
+    fn from(polygon: Polygon) -> Self {
+        Primitive::Polygon(polygon)
+    }
+}
+
+// The shape is given in the compound's own frame. It is rotated about that origin
+// and then moved by the offset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundChild {
+    pub shape: Primitive,
+    pub offset: Vector,
+    pub rotation: Angle,
+}
+
+impl CompoundChild {
+    pub fn new(shape: impl Into<Primitive>, offset: Vector, rotation: Angle) -> Self {
+        Self {
+            shape: shape.into(),
+            offset,
+            rotation,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CompoundShape {
+    center: Point,
+    phi: Angle,
+    children: Vec<CompoundChild>,
+    // the children moved into world coordinates
+    _placed: OnceLock<Vec<Primitive>>,
+    _polygon: OnceLock<Polygon>,
+    _bounding_box: OnceLock<BoundingBox>,
+}
+
+impl CompoundShape {
+    pub fn new(center: Point, phi: Angle, children: Vec<CompoundChild>) -> Self {
+        Self {
+            center,
+            phi,
+            children,
+            _placed: OnceLock::new(),
+            _polygon: OnceLock::new(),
+            _bounding_box: OnceLock::new(),
+        }
+    }
+    pub fn phi(&self) -> Angle {
+        self.phi
+    }
+    pub fn children(&self) -> &[CompoundChild] {
+        &self.children
+    }
+    pub fn push(&mut self, child: CompoundChild) {
+        self.children.push(child);
+        self.invalidate();
+    }
+    // in the same order as `children`
+    pub fn placed(&self) -> &[Primitive] {
+        self._placed.get_or_init(|| {
+            self.children
+                .iter()
+                .map(|child| {
+                    let mut placed = child.shape.clone();
+                    let shape = placed.shape_mut();
+                    shape.rotate_about(Point::zero(), child.rotation);
+                    shape.translate(child.offset);
+                    shape.rotate_about(Point::zero(), self.phi);
+                    shape.translate(self.center.to_vector());
+                    placed
+                })
+                .collect()
+        })
+    }
+    // the hit with the largest delta and the index of the child it came from
+    pub fn deepest_hit<F>(&self, mut test: F) -> Option<(usize, Hit)>
+    where
+        F: FnMut(&Primitive) -> Option<Hit>,
+    {
+        let mut deepest: Option<(usize, Hit)> = None;
+        for (index, child) in self.placed().iter().enumerate() {
+            if let Some(hit) = test(child) {
+                let depth = hit.delta.magnitude();
+                if deepest.map_or(true, |(_, best)| depth > best.delta.magnitude()) {
+                    deepest = Some((index, hit));
+                }
+            }
+        }
+        deepest
+    }
+    fn invalidate(&mut self) {
+        self._placed = OnceLock::new();
+        self._polygon = OnceLock::new();
+        self._bounding_box = OnceLock::new();
+    }
+    fn create_bounding_box(&self) -> BoundingBox {
+        self.placed()
+            .iter()
+            .map(|child| child.shape().bounding_box())
+            .reduce(|union, bounds| union.union(bounds))
+            .unwrap_or_else(|| BoundingBox::new(self.center, Size::new(0.0, 0.0)))
+    }
+}
+
+// the caches are left out, they only hold what the other fields determine
+impl PartialEq for CompoundShape {
+    fn eq(&self, other: &Self) -> bool {
+        self.center == other.center && self.phi == other.phi && self.children == other.children
+    }
+}
+
+// Area and mass add up the children, so overlapping parts count twice. The outline
+// is the convex hull around all of them.
+impl Shape for CompoundShape {
+    fn get_type(&self) -> ShapeType {
+        ShapeType::Compound
+    }
+    fn center(&self) -> Point {
+        self.center
+    }
+    fn enclosing_radius(&self) -> f32 {
+        self.placed()
+            .iter()
+            .map(|child| {
+                let shape = child.shape();
+                shape.center().distance_to(self.center) + shape.enclosing_radius()
+            })
+            .fold(0.0, f32::max)
+    }
+    fn translate(&mut self, vector: Vector) {
+        self.center = self.center + vector;
+        self.invalidate();
+    }
+    fn move_to(&mut self, point: Point) {
+        self.center = point;
+        self.invalidate();
+    }
+    fn rotate(&mut self, theta: Angle) {
+        self.phi = self.phi + theta;
+        self.invalidate();
+    }
+    fn rotate_to(&mut self, phi: Angle) {
+        self.phi = phi;
+        self.invalidate();
+    }
+    fn pose(&self) -> Pose {
+        Pose::new(self.center, self.phi)
+    }
+    fn rotate_about(&mut self, point: Point, theta: Angle) {
+        self.center.rotate_about(point, theta);
+        self.phi = self.phi + theta;
+        self.invalidate();
+    }
+    fn vertices(&self) -> &[Point] {
+        &self
+            ._polygon
+            .get_or_init(|| {
+                let points: Vec<Point> = self
+                    .placed()
+                    .iter()
+                    .flat_map(|child| child.shape().vertices().iter().copied())
+                    .collect();
+                convex_hull(&points)
+            })
+            .vertices
+    }
+    fn bounding_box(&self) -> BoundingBox {
+        *self
+            ._bounding_box
+            .get_or_init(|| self.create_bounding_box())
+    }
+    fn closest_point(&self, point: Point) -> Point {
+        self.placed()
+            .iter()
+            .map(|child| child.shape().closest_point(point))
+            .min_by(|a, b| {
+                let (da, db) = (a.distance_to(point), b.distance_to(point));
+                da.partial_cmp(&db).unwrap()
+            })
+            .unwrap_or(self.center)
+    }
+    // exact outside, inside overlapping children only give a bound
+    fn signed_distance(&self, point: Point) -> f32 {
+        self.placed()
+            .iter()
+            .map(|child| child.shape().signed_distance(point))
+            .fold(f32::MAX, f32::min)
+    }
+    fn area(&self) -> f32 {
+        self.placed().iter().map(|child| child.shape().area()).sum()
+    }
+    fn centroid(&self) -> Point {
+        if self.children.is_empty() {
+            return self.center;
+        }
+        self.mass_properties(1.0).centroid
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        let parts: Vec<MassProperties> = self
+            .placed()
+            .iter()
+            .map(|child| child.shape().mass_properties(density))
+            .collect();
+        MassProperties::combine(&parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point, Size, Vector};
+    use crate::collision::{BoundingBox, Collidable};
+    use crate::shape::{shape::Shape, Circle, CompoundChild, CompoundShape, Primitive, Rectangle};
+
+    // a body with a wheel under each end
+    fn vehicle(center: Point, phi: Angle) -> CompoundShape {
+        let body = Rectangle::new(Point::zero(), Size::new(8.0, 2.0), Angle::zero());
+        let wheel = Circle::new(Point::zero(), 1.0);
+        CompoundShape::new(
+            center,
+            phi,
+            vec![
+                CompoundChild::new(body, Vector::new(0.0, 1.0), Angle::zero()),
+                CompoundChild::new(wheel.clone(), Vector::new(-3.0, -1.0), Angle::zero()),
+                CompoundChild::new(wheel, Vector::new(3.0, -1.0), Angle::zero()),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_placement() {
+        let mut compound = vehicle(Point::new(10.0, 0.0), Angle::new(90.0));
+        // the offsets turn with the compound
+        assert_eq!(
+            compound.placed()[1].shape().center(),
+            Point::new(11.0, -3.0)
+        );
+        if let Primitive::Rectangle(body) = &compound.placed()[0] {
+            assert_eq!(body.center(), Point::new(9.0, 0.0));
+            assert_eq!(
+                body.bounding_box(),
+                BoundingBox::from_edges(8.0, -4.0, 10.0, 4.0)
+            );
+        } else {
+            panic!("Expected the body first");
+        }
+        compound.translate(Vector::new(-10.0, 0.0));
+        compound.rotate_to(Angle::zero());
+        assert_eq!(
+            compound.bounding_box(),
+            BoundingBox::from_edges(-4.0, -2.0, 4.0, 2.0)
+        );
+        assert_eq!(compound, vehicle(Point::zero(), Angle::zero()));
+    }
+    #[test]
+    fn test_shape() {
+        let compound = vehicle(Point::zero(), Angle::zero());
+        assert_eq!(
+            compound.closest_point(Point::new(3.0, -5.0)),
+            Point::new(3.0, -2.0)
+        );
+        assert_eq!(
+            compound.closest_point(Point::new(0.0, 5.0)),
+            Point::new(0.0, 2.0)
+        );
+        let expected = 16.0 + 2.0 * std::f32::consts::PI;
+        assert!((compound.area() - expected).abs() < 1e-4);
+        // the wheels pull the centroid down
+        assert!(compound.centroid().y < 1.0 && compound.centroid().y > 0.0);
+        assert_eq!(compound.centroid().x, 0.0);
+        // the hull spans the body and hangs round the wheels
+        let hull = compound.polygon();
+        assert!(hull.is_inside(Point::new(0.0, -1.5)));
+        assert!(!hull.is_inside(Point::new(0.0, -2.5)));
+        // the corners of the body reach furthest
+        assert!((compound.enclosing_radius() - (1.0 + 17f32.sqrt())).abs() < 1e-4);
+        let empty = CompoundShape::new(Point::new(1.0, 1.0), Angle::zero(), Vec::new());
+        assert_eq!(empty.closest_point(Point::zero()), Point::new(1.0, 1.0));
+        assert_eq!(empty.area(), 0.0);
+    }
+    #[test]
+    fn test_deepest_hit() {
+        let compound = vehicle(Point::zero(), Angle::zero());
+        // grazes the body, but sits deep inside the right wheel
+        let ball = Circle::new(Point::new(3.5, -1.5), 0.5);
+        let (index, hit) = compound
+            .deepest_hit(|child| child.hit_circle(&ball))
+            .unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(compound.hit_circle(&ball), Some(hit));
+        assert!(compound.hit_point(Point::new(0.0, 5.0)).is_none());
+        let (index, _) = compound
+            .deepest_hit(|child| child.hit_point(Point::new(0.0, 1.5)))
+            .unwrap();
+        assert_eq!(index, 0);
+    }
+}
@@ -1,15 +1,15 @@
-use crate::base::{Angle, Point, Vector};
+use crate::base::{Angle, Point, Pose, Vector};
 use crate::collision::BoundingBox;
-use crate::shape::{shape::*, Polygon};
-use std::cell::RefCell;
+use crate::shape::{shape::*, Circle, MassProperties, Polygon};
+use std::sync::OnceLock;
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default)]
 pub struct Hexagon {
     center: Point,
     side: f32,
     phi: Angle,
-    _polygon: RefCell<Option<Polygon>>,
-    _bounding_box: RefCell<Option<BoundingBox>>,
+    _polygon: OnceLock<Polygon>,
+    _bounding_box: OnceLock<BoundingBox>,
 }
 
 impl Hexagon {
@@ -18,27 +18,39 @@ impl Hexagon {
             center,
             side,
             phi,
-            _polygon: RefCell::new(None),
-            _bounding_box: RefCell::new(None),
+            _polygon: OnceLock::new(),
+            _bounding_box: OnceLock::new(),
         }
     }
-    fn invalidate(&self) {
-        *self._polygon.borrow_mut() = None;
-        *self._bounding_box.borrow_mut() = None;
+    pub fn side(&self) -> f32 {
+        self.side
+    }
+    pub fn orientation(&self) -> Angle {
+        self.phi
+    }
+    pub(crate) fn cached_polygon(&self) -> &Polygon {
+        self._polygon.get_or_init(|| self.create_polygon())
     }
-    fn create_polygon(&self) {
-        let mut vertices = Vec::new();
-        vertices.reserve(6);
+    fn invalidate(&mut self) {
+        self._polygon = OnceLock::new();
+        self._bounding_box = OnceLock::new();
+    }
+    fn create_polygon(&self) -> Polygon {
+        let mut vertices = Vec::with_capacity(6);
         for i in 0..6 {
             let theta = self.phi + Angle::new(i as f64 * 60f64);
             let xh = self.center.x + (theta.cos() as f32) * self.side;
             let yh = self.center.y + (theta.sin() as f32) * self.side;
             vertices.push(Point::new(xh, yh));
         }
-        *self._polygon.borrow_mut() = Some(Polygon { vertices });
+        Polygon { vertices }
     }
-    fn create_bounding_box(&self) {
-        *self._bounding_box.borrow_mut() = Some(self.polygon().to_bounding_box());
+}
+
+// the caches are left out, they only hold what the other fields determine
+impl PartialEq for Hexagon {
+    fn eq(&self, other: &Self) -> bool {
+        self.center == other.center && self.side == other.side && self.phi == other.phi
     }
 }
 
@@ -68,26 +80,41 @@ impl Shape for Hexagon {
         self.phi = phi;
         self.invalidate();
     }
+    fn pose(&self) -> Pose {
+        Pose::new(self.center, self.phi)
+    }
     fn rotate_about(&mut self, point: Point, theta: Angle) {
         self.center.rotate_about(point, theta);
         self.phi = self.phi + theta;
         self.invalidate();
     }
-    fn polygon(&self) -> Polygon {
-        if *self._polygon.borrow() == None {
-            self.create_polygon();
-        }
-        (*self._polygon.borrow()).clone().unwrap()
+    fn vertices(&self) -> &[Point] {
+        &self.cached_polygon().vertices
     }
     fn bounding_box(&self) -> BoundingBox {
-        if *self._bounding_box.borrow() == None {
-            self.create_bounding_box();
-        }
-        (*self._bounding_box.borrow()).clone().unwrap()
+        *self
+            ._bounding_box
+            .get_or_init(|| self.cached_polygon().to_bounding_box())
     }
     fn closest_point(&self, point: Point) -> Point {
-        let polygon = self.polygon();
-        polygon.closest_point(point)
+        self.cached_polygon().closest_point(point)
+    }
+    fn area(&self) -> f32 {
+        1.5 * 3f32.sqrt() * self.side.powf(2.0)
+    }
+    fn perimeter(&self) -> f32 {
+        6.0 * self.side
+    }
+    fn centroid(&self) -> Point {
+        self.center
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        // regular hexagons are isotropic, so the orientation does not matter
+        let moment = density * 5.0 * 3f32.sqrt() / 16.0 * self.side.powf(4.0);
+        MassProperties::new(density * self.area(), self.center, moment, moment, 0.0)
+    }
+    fn enclosing_circle(&self) -> Circle {
+        Circle::new(self.center, self.side)
     }
 }
 
@@ -118,6 +145,23 @@ mod tests {
         assert_eq!(hexagon, expected);
     }
     #[test]
+    fn test_vertices_edges() {
+        let mut hexagon = Hexagon::new(Point::new(10.0, -5.0), 2.0, Angle::zero());
+        // borrowed from the cache, so repeated calls see the same storage
+        assert_eq!(hexagon.vertices().as_ptr(), hexagon.vertices().as_ptr());
+        assert_eq!(hexagon.vertices(), &hexagon.polygon().vertices[..]);
+        assert_eq!(hexagon.edges().len(), 6);
+        let perimeter: f32 = hexagon
+            .edges()
+            .map(|edge| edge.to_vector().magnitude())
+            .sum();
+        assert!((perimeter - hexagon.perimeter()).abs() < 1e-4);
+        hexagon.translate(Vector::new(1.0, 0.0));
+        assert_eq!(hexagon.vertices()[0], Point::new(13.0, -5.0));
+        let contact = hexagon.contact_point(Point::new(0.0, -5.0), Vector::new(1.0, 0.0));
+        assert_eq!(contact, Some(Point::new(9.0, -5.0)));
+    }
+    #[test]
     fn test_to_polygon_flat_topped() {
         let hexagon = Hexagon::new(Point::new(10.0, -5.0), 2.0, Angle::zero());
         let poly = hexagon.polygon();
@@ -179,4 +223,22 @@ mod tests {
         let expected = Point::new(0.0, -10.0 * 3.0f32.sqrt() / 2.0);
         assert_eq!(result, expected);
     }
+    #[test]
+    fn test_area_perimeter_centroid() {
+        let hexagon = Hexagon::new(Point::new(10.0, -5.0), 2.0, Angle::new(15f64));
+        assert_eq!(hexagon.area(), 6.0 * 3f32.sqrt());
+        assert_eq!(hexagon.perimeter(), 12.0);
+        assert_eq!(hexagon.centroid(), Point::new(10.0, -5.0));
+        assert!((hexagon.area() - hexagon.polygon().area()).abs() < 1e-4);
+    }
+    #[test]
+    fn test_mass_properties() {
+        let hexagon = Hexagon::new(Point::new(1.0, -2.0), 3.0, Angle::new(20f64));
+        let exact = hexagon.mass_properties(0.5);
+        let approx = hexagon.polygon().mass_properties(0.5);
+        assert!((exact.mass - approx.mass).abs() < 1e-3);
+        assert!((exact.ixx - approx.ixx).abs() < 1e-3);
+        assert!((exact.iyy - approx.iyy).abs() < 1e-3);
+        assert!(approx.ixy.abs() < 1e-3);
+    }
 }
@@ -0,0 +1,209 @@
+use crate::base::{Angle, Point, Vector};
+
+// Second moments are taken about the centroid: ixx = ∫y² dm, iyy = ∫x² dm and ixy = ∫xy dm.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MassProperties {
+    pub mass: f32,
+    pub centroid: Point,
+    pub ixx: f32,
+    pub iyy: f32,
+    pub ixy: f32,
+}
+
+#[allow(dead_code)]
+impl MassProperties {
+    pub fn new(mass: f32, centroid: Point, ixx: f32, iyy: f32, ixy: f32) -> Self {
+        Self {
+            mass,
+            centroid,
+            ixx,
+            iyy,
+            ixy,
+        }
+    }
+    pub fn zero() -> Self {
+        Self::new(0.0, Point::zero(), 0.0, 0.0, 0.0)
+    }
+    pub fn from_polygon(vertices: &[Point], density: f32) -> Self {
+        let reference = match vertices.first() {
+            Some(&vertex) => vertex,
+            None => return Self::zero(),
+        };
+        // integrate relative to the first vertex in f64, then shift to the centroid
+        let n = vertices.len();
+        let mut area = 0f64;
+        let mut cx = 0f64;
+        let mut cy = 0f64;
+        let mut xx = 0f64;
+        let mut yy = 0f64;
+        let mut xy = 0f64;
+        for i in 0..n {
+            let a = vertices[i] - reference;
+            let b = vertices[(i + 1) % n] - reference;
+            let (x0, y0, x1, y1) = (a.x as f64, a.y as f64, b.x as f64, b.y as f64);
+            let cross = x0 * y1 - x1 * y0;
+            area += cross;
+            cx += (x0 + x1) * cross;
+            cy += (y0 + y1) * cross;
+            xx += (x0 * x0 + x0 * x1 + x1 * x1) * cross;
+            yy += (y0 * y0 + y0 * y1 + y1 * y1) * cross;
+            xy += (x0 * y1 + 2.0 * x0 * y0 + 2.0 * x1 * y1 + x1 * y0) * cross;
+        }
+        if area == 0.0 {
+            return Self::new(0.0, reference, 0.0, 0.0, 0.0);
+        }
+        let area = area / 2.0;
+        let (cx, cy) = (cx / (6.0 * area), cy / (6.0 * area));
+        // clockwise polygons yield negative integrals
+        let sign = area.signum();
+        let area = area.abs();
+        let xx = sign * xx / 12.0 - area * cx * cx;
+        let yy = sign * yy / 12.0 - area * cy * cy;
+        let xy = sign * xy / 24.0 - area * cx * cy;
+        let density = density as f64;
+        Self::new(
+            (density * area) as f32,
+            reference + Point::new(cx as f32, cy as f32),
+            (density * yy) as f32,
+            (density * xx) as f32,
+            (density * xy) as f32,
+        )
+    }
+    pub fn combine(parts: &[MassProperties]) -> Self {
+        parts.iter().fold(Self::zero(), |acc, &part| acc + part)
+    }
+    pub fn inertia(&self) -> f32 {
+        self.ixx + self.iyy
+    }
+    pub fn inertia_about(&self, point: Point) -> f32 {
+        self.inertia() + self.mass * self.centroid.squared_distance_to(point)
+    }
+    pub fn principal_axes(&self) -> (Angle, f32, f32) {
+        // eigen decomposition of the covariance [[iyy, ixy], [ixy, ixx]]
+        let mean = (self.iyy + self.ixx) / 2.0;
+        let half_diff = (self.iyy - self.ixx) / 2.0;
+        let radius = (half_diff.powf(2.0) + self.ixy.powf(2.0)).sqrt();
+        let theta = (2.0 * self.ixy).atan2(self.iyy - self.ixx) / 2.0;
+        // the axis along the largest spread has the smallest moment of inertia
+        (
+            Angle::from_radian(theta as f64),
+            mean - radius,
+            mean + radius,
+        )
+    }
+    fn moments_about(&self, point: Point) -> (f32, f32, f32) {
+        let d = Vector::from_points(point, self.centroid);
+        (
+            self.ixx + self.mass * d.dy * d.dy,
+            self.iyy + self.mass * d.dx * d.dx,
+            self.ixy + self.mass * d.dx * d.dy,
+        )
+    }
+    fn merge(self, other: MassProperties, sign: f32) -> Self {
+        let mass = self.mass + sign * other.mass;
+        if mass == 0.0 {
+            return Self::zero();
+        }
+        let centroid = (self.centroid.to_vector() * self.mass
+            + other.centroid.to_vector() * sign * other.mass)
+            / mass;
+        let centroid = centroid.to_point();
+        let (ixx_a, iyy_a, ixy_a) = self.moments_about(centroid);
+        let (ixx_b, iyy_b, ixy_b) = other.moments_about(centroid);
+        Self::new(
+            mass,
+            centroid,
+            ixx_a + sign * ixx_b,
+            iyy_a + sign * iyy_b,
+            ixy_a + sign * ixy_b,
+        )
+    }
+}
+
+impl std::ops::Add<MassProperties> for MassProperties {
+    type Output = Self;
+    fn add(self, other: MassProperties) -> Self {
+        self.merge(other, 1.0)
+    }
+}
+
+impl std::ops::Sub<MassProperties> for MassProperties {
+    type Output = Self;
+    fn sub(self, other: MassProperties) -> Self {
+        self.merge(other, -1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point};
+    use crate::shape::MassProperties;
+
+    fn rectangle(x1: f32, y1: f32, x2: f32, y2: f32) -> Vec<Point> {
+        vec![
+            Point::new(x1, y1),
+            Point::new(x2, y1),
+            Point::new(x2, y2),
+            Point::new(x1, y2),
+        ]
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3 * b.abs().max(1.0), "{} == {}", a, b);
+    }
+
+    #[test]
+    fn test_from_polygon() {
+        let props = MassProperties::from_polygon(&rectangle(1.0, 1.0, 5.0, 3.0), 2.0);
+        let mass = 2.0 * 8.0;
+        assert_close(props.mass, mass);
+        assert_eq!(props.centroid, Point::new(3.0, 2.0));
+        assert_close(props.ixx, mass * 4.0 / 12.0);
+        assert_close(props.iyy, mass * 16.0 / 12.0);
+        assert_close(props.ixy, 0.0);
+        // winding does not matter
+        let mut reversed = rectangle(1.0, 1.0, 5.0, 3.0);
+        reversed.reverse();
+        let props_cw = MassProperties::from_polygon(&reversed, 2.0);
+        assert_close(props_cw.mass, mass);
+        assert_close(props_cw.inertia(), props.inertia());
+    }
+    #[test]
+    fn test_inertia_about() {
+        let props = MassProperties::from_polygon(&rectangle(-1.0, -1.0, 1.0, 1.0), 1.0);
+        // square of mass 4 and side 2: I = m (w² + h²) / 12
+        assert_close(props.inertia(), 4.0 * 8.0 / 12.0);
+        // parallel axis theorem
+        let about = props.inertia_about(Point::new(3.0, 4.0));
+        assert_close(about, 4.0 * 8.0 / 12.0 + 4.0 * 25.0);
+    }
+    #[test]
+    fn test_combine() {
+        let left = MassProperties::from_polygon(&rectangle(0.0, 0.0, 1.0, 1.0), 1.0);
+        let right = MassProperties::from_polygon(&rectangle(1.0, 0.0, 2.0, 1.0), 1.0);
+        let whole = MassProperties::from_polygon(&rectangle(0.0, 0.0, 2.0, 1.0), 1.0);
+        let combined = MassProperties::combine(&[left, right]);
+        assert_close(combined.mass, whole.mass);
+        assert_eq!(combined.centroid, whole.centroid);
+        assert_close(combined.ixx, whole.ixx);
+        assert_close(combined.iyy, whole.iyy);
+        // removing a part again restores the original
+        let restored = combined - right;
+        assert_close(restored.mass, left.mass);
+        assert_eq!(restored.centroid, left.centroid);
+        assert_close(restored.inertia(), left.inertia());
+    }
+    #[test]
+    fn test_principal_axes() {
+        // 4 x 2 rectangle rotated by 30 degrees
+        let mut vertices = rectangle(-2.0, -1.0, 2.0, 1.0);
+        for vertex in vertices.iter_mut() {
+            vertex.rotate_about(Point::zero(), Angle::new(30f64));
+        }
+        let props = MassProperties::from_polygon(&vertices, 1.0);
+        let (angle, i_min, i_max) = props.principal_axes();
+        assert!((angle.deg - 30.0).abs() < 1e-2, "{}", angle);
+        assert_close(i_min, 8.0 * 4.0 / 12.0);
+        assert_close(i_max, 8.0 * 16.0 / 12.0);
+    }
+}
@@ -1,11 +1,27 @@
+pub use self::any_shape::{AnyShape, ShapeValue};
 pub use self::circle::Circle;
+pub use self::compound_shape::{CompoundChild, CompoundShape, Primitive};
 pub use self::hexagon::Hexagon;
-pub use self::polygon::Polygon;
+pub use self::mass_properties::MassProperties;
+pub use self::multi_polygon::MultiPolygon;
+pub use self::polygon::{Edges, PointLocation, Polygon};
+pub use self::polygon_with_holes::PolygonWithHoles;
+pub use self::polyline::Polyline;
 pub use self::rectangle::Rectangle;
 pub use self::shape::*;
+pub use self::tessellation::Tessellation;
+pub use self::tween::{Easing, Tween};
 
+mod any_shape;
 mod circle;
+mod compound_shape;
 mod hexagon;
+mod mass_properties;
+mod multi_polygon;
 mod polygon;
+mod polygon_with_holes;
+mod polyline;
 mod rectangle;
 mod shape;
+mod tessellation;
+mod tween;
@@ -0,0 +1,212 @@
+use crate::algorithm::offset::{offset_rings, JoinStyle};
+use crate::base::{Angle, Line, Point, Vector};
+use crate::collision::BoundingBox;
+use crate::shape::{MassProperties, PointLocation, Polygon, PolygonWithHoles, Tessellation};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultiPolygon {
+    pub polygons: Vec<PolygonWithHoles>,
+}
+
+#[allow(dead_code)]
+impl MultiPolygon {
+    pub fn new(polygons: Vec<PolygonWithHoles>) -> Self {
+        Self { polygons }
+    }
+    pub fn len(&self) -> usize {
+        self.polygons.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+    pub fn edges(&self) -> impl Iterator<Item = Line> + '_ {
+        self.polygons.iter().flat_map(|polygon| polygon.edges())
+    }
+    pub fn offset(&self, distance: f32, join: JoinStyle) -> Option<MultiPolygon> {
+        self.offset_with(distance, join, Tessellation::default())
+    }
+    pub fn offset_with(
+        &self,
+        distance: f32,
+        join: JoinStyle,
+        tessellation: Tessellation,
+    ) -> Option<MultiPolygon> {
+        let rings: Vec<Vec<Point>> = self
+            .polygons
+            .iter()
+            .flat_map(|polygon| polygon.oriented_rings())
+            .collect();
+        let rings: Vec<&[Point]> = rings.iter().map(|ring| &ring[..]).collect();
+        offset_rings(&rings, distance, join, tessellation)
+    }
+    pub fn locate(&self, point: Point) -> PointLocation {
+        let mut location = PointLocation::Outside;
+        for polygon in self.polygons.iter() {
+            match polygon.locate(point) {
+                PointLocation::Inside => return PointLocation::Inside,
+                PointLocation::Boundary => location = PointLocation::Boundary,
+                PointLocation::Outside => (),
+            }
+        }
+        location
+    }
+    pub fn is_inside(&self, point: Point) -> bool {
+        self.locate(point) == PointLocation::Inside
+    }
+    pub fn area(&self) -> f32 {
+        self.polygons.iter().map(|polygon| polygon.area()).sum()
+    }
+    pub fn perimeter(&self) -> f32 {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.perimeter())
+            .sum()
+    }
+    pub fn centroid(&self) -> Point {
+        let mut area = 0.0;
+        let mut moment = Vector::zero();
+        for polygon in self.polygons.iter() {
+            let polygon_area = polygon.area();
+            area += polygon_area;
+            moment = moment + polygon.centroid().to_vector() * polygon_area;
+        }
+        if area == 0.0 {
+            return self.center();
+        }
+        (moment / area).to_point()
+    }
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        let parts: Vec<MassProperties> = self
+            .polygons
+            .iter()
+            .map(|polygon| polygon.mass_properties(density))
+            .collect();
+        MassProperties::combine(&parts)
+    }
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut boxes = self.polygons.iter().map(|polygon| polygon.bounding_box());
+        match boxes.next() {
+            Some(first) => boxes.fold(first, |acc, bounding_box| acc.union(bounding_box)),
+            None => BoundingBox::default(),
+        }
+    }
+    pub fn closest_point(&self, point: Point) -> Point {
+        let mut polygons = self.polygons.iter();
+        let mut best = match polygons.next() {
+            Some(first) => first.closest_point(point),
+            None => return self.center(),
+        };
+        for polygon in polygons {
+            let candidate = polygon.closest_point(point);
+            if candidate.distance_to(point) < best.distance_to(point) {
+                best = candidate;
+            }
+        }
+        best
+    }
+    pub fn center(&self) -> Point {
+        self.bounding_box().center
+    }
+    pub fn translate(&mut self, vector: Vector) {
+        for polygon in self.polygons.iter_mut() {
+            polygon.translate(vector);
+        }
+    }
+    pub fn move_to(&mut self, point: Point) {
+        let vector = Vector::from_points(self.center(), point);
+        self.translate(vector);
+    }
+    pub fn rotate(&mut self, theta: Angle) {
+        let center = self.center();
+        self.rotate_about(center, theta);
+    }
+    pub fn rotate_about(&mut self, point: Point, theta: Angle) {
+        for polygon in self.polygons.iter_mut() {
+            polygon.rotate_about(point, theta);
+        }
+    }
+}
+
+impl From<Polygon> for MultiPolygon {
+    fn from(polygon: Polygon) -> Self {
+        Self::new(vec![polygon.into()])
+    }
+}
+
+impl From<PolygonWithHoles> for MultiPolygon {
+    fn from(polygon: PolygonWithHoles) -> Self {
+        Self::new(vec![polygon])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::offset::JoinStyle;
+    use crate::base::Point;
+    use crate::collision::BoundingBox;
+    use crate::shape::{MultiPolygon, PointLocation, Polygon, PolygonWithHoles};
+
+    fn islands() -> MultiPolygon {
+        MultiPolygon::new(vec![
+            PolygonWithHoles::new(
+                Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+                vec![Polygon::new(
+                    &BoundingBox::from_edges(2.0, 2.0, 8.0, 8.0).corners(),
+                )],
+            ),
+            Polygon::new(&BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners()).into(),
+            Polygon::new(&BoundingBox::from_edges(20.0, 0.0, 22.0, 3.0).corners()).into(),
+        ])
+    }
+
+    #[test]
+    fn test_locate() {
+        let multi = islands();
+        assert_eq!(multi.locate(Point::new(1.0, 1.0)), PointLocation::Inside);
+        // island inside the courtyard
+        assert_eq!(multi.locate(Point::new(5.0, 5.0)), PointLocation::Inside);
+        assert_eq!(multi.locate(Point::new(3.0, 3.0)), PointLocation::Outside);
+        assert_eq!(multi.locate(Point::new(21.0, 3.0)), PointLocation::Boundary);
+        assert_eq!(multi.locate(Point::new(15.0, 1.0)), PointLocation::Outside);
+    }
+    #[test]
+    fn test_area() {
+        let multi = islands();
+        assert_eq!(multi.area(), 100.0 - 36.0 + 4.0 + 6.0);
+    }
+    #[test]
+    fn test_bounding_box() {
+        let multi = islands();
+        let expected = BoundingBox::from_edges(0.0, 0.0, 22.0, 10.0);
+        assert_eq!(multi.bounding_box(), expected);
+    }
+    #[test]
+    fn test_closest_point() {
+        let multi = islands();
+        let result = multi.closest_point(Point::new(16.0, 1.0));
+        assert_eq!(result, Point::new(20.0, 1.0));
+        // nothing to be close to, like the bounding box it falls back to the default
+        let empty = MultiPolygon::new(Vec::new());
+        assert_eq!(empty.closest_point(Point::new(3.0, 4.0)), Point::zero());
+    }
+    #[test]
+    fn test_centroid() {
+        let multi = MultiPolygon::new(vec![
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners()).into(),
+            Polygon::new(&BoundingBox::from_edges(10.0, 0.0, 12.0, 2.0).corners()).into(),
+        ]);
+        assert_eq!(multi.centroid(), Point::new(6.0, 1.0));
+        assert_eq!(multi.perimeter(), 16.0);
+    }
+    #[test]
+    fn test_offset() {
+        // neighbouring parts merge when grown
+        let parts = MultiPolygon::new(vec![
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 2.0, 2.0).corners()).into(),
+            Polygon::new(&BoundingBox::from_edges(3.0, 0.0, 5.0, 2.0).corners()).into(),
+        ]);
+        let grown = parts.offset(1.0, JoinStyle::Miter(2.0)).unwrap();
+        assert_eq!(grown.len(), 1);
+        assert!((grown.area() - 28.0).abs() < 1e-3);
+    }
+}
@@ -1,5 +1,22 @@
-use crate::base::{Angle, Line, Point, Vector};
+use crate::algorithm::calipers;
+use crate::algorithm::decomposition::{bayazit, hertel_mehlhorn};
+use crate::algorithm::enclosing::minimum_enclosing_circle;
+use crate::algorithm::hull::convex_hull;
+use crate::algorithm::minkowski;
+use crate::algorithm::offset::{offset_rings, JoinStyle};
+use crate::algorithm::simplify::{douglas_peucker, visvalingam_whyatt, Target};
+use crate::algorithm::triangulation;
+use crate::algorithm::validation::{self, Orientation, ValidationReport};
+use crate::base::{Angle, Line, Point, Pose, Triangle, Vector};
 use crate::collision::BoundingBox;
+use crate::shape::{shape::*, Circle, MassProperties, MultiPolygon, Tessellation};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Polygon {
@@ -8,30 +25,161 @@ pub struct Polygon {
 
 #[allow(dead_code)]
 impl Polygon {
-    pub fn new(vertices: &Vec<Point>) -> Self {
-        let p = vertices.clone();
+    pub fn new(vertices: &[Point]) -> Self {
+        let p = vertices.to_vec();
         Self { vertices: p }
     }
-    pub fn from_vectors(start: Point, vectors: &Vec<Vector>) -> Self {
+    // reverses clockwise input, so the filled area is always on the left of the edges
+    pub fn new_counter_clockwise(vertices: &[Point]) -> Self {
+        let mut polygon = Self::new(vertices);
+        if polygon.orientation() == Orientation::Clockwise {
+            polygon.vertices.reverse();
+        }
+        polygon
+    }
+    pub fn from_vectors(start: Point, vectors: &[Vector]) -> Self {
         let mut vertices = Vec::new();
         vertices.push(start);
-        for vector in vectors.iter() {
-            vertices.push(vertices.last().unwrap().clone() + vector.clone());
+        for &vector in vectors.iter() {
+            vertices.push(*vertices.last().unwrap() + vector);
         }
         Self { vertices }
     }
+    pub fn edges(&self) -> Edges<'_> {
+        Edges::new(&self.vertices)
+    }
     pub fn to_lines(&self) -> Vec<Line> {
-        let mut lines = Vec::new();
-        let n = self.vertices.len();
-        for i in 0..n {
-            lines.push(Line::new(self.vertices[i], self.vertices[(i + 1) % n]));
+        self.edges().collect()
+    }
+    pub fn signed_area(&self) -> f32 {
+        let mut sum = 0.0;
+        for edge in self.edges() {
+            sum += edge.origin.x * edge.end.y - edge.end.x * edge.origin.y;
+        }
+        sum / 2.0
+    }
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+    pub fn orientation(&self) -> Orientation {
+        validation::orientation(&self.vertices)
+    }
+    pub fn validate(&self) -> ValidationReport {
+        validation::validate(&self.vertices)
+    }
+    pub fn make_valid(&self) -> Option<MultiPolygon> {
+        validation::make_valid(&self.vertices)
+    }
+    pub fn perimeter(&self) -> f32 {
+        self.edges()
+            .map(|edge| edge.origin.distance_to(edge.end))
+            .sum()
+    }
+    pub fn centroid(&self) -> Point {
+        let reference = match self.vertices.first() {
+            Some(&vertex) => vertex,
+            None => return Point::zero(),
+        };
+        // relative to the first vertex to keep the products small
+        let mut area = 0.0;
+        let mut cx = 0.0;
+        let mut cy = 0.0;
+        for edge in self.edges() {
+            let a = edge.origin - reference;
+            let b = edge.end - reference;
+            let cross = a.x * b.y - b.x * a.y;
+            area += cross;
+            cx += (a.x + b.x) * cross;
+            cy += (a.y + b.y) * cross;
+        }
+        if area == 0.0 {
+            // degenerate polygon, fall back to the vertex average
+            let sum = self
+                .vertices
+                .iter()
+                .fold(Point::zero(), |acc, &vertex| acc + vertex);
+            return sum / self.vertices.len() as f32;
+        }
+        reference + Point::new(cx, cy) / (3.0 * area)
+    }
+    pub fn convex_hull(&self) -> Polygon {
+        convex_hull(&self.vertices)
+    }
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        MassProperties::from_polygon(&self.vertices, density)
+    }
+    // grows the polygon for positive distances and shrinks it for negative ones,
+    // which may split it into several parts or make it vanish
+    pub fn offset(&self, distance: f32, join: JoinStyle) -> Option<MultiPolygon> {
+        self.offset_with(distance, join, Tessellation::default())
+    }
+    // as `offset`, with round joins cut into as many segments as `tessellation` asks for
+    pub fn offset_with(
+        &self,
+        distance: f32,
+        join: JoinStyle,
+        tessellation: Tessellation,
+    ) -> Option<MultiPolygon> {
+        let ring = Self::new_counter_clockwise(&self.vertices).vertices;
+        offset_rings(&[&ring], distance, join, tessellation)
+    }
+    pub fn simplify(&self, target: Target, preserve_topology: bool) -> Polygon {
+        Polygon {
+            vertices: douglas_peucker(&self.vertices, true, target, preserve_topology),
+        }
+    }
+    pub fn simplify_by_area(&self, target: Target, preserve_topology: bool) -> Polygon {
+        Polygon {
+            vertices: visvalingam_whyatt(&self.vertices, true, target, preserve_topology),
         }
-        lines
+    }
+    // the calipers measurements work on the convex hull
+    pub fn diameter(&self) -> (f32, Point, Point) {
+        calipers::diameter(&self.vertices)
+    }
+    pub fn width(&self) -> (f32, Vector) {
+        calipers::width(&self.vertices)
+    }
+    pub fn antipodal_pairs(&self) -> Vec<(Point, Point)> {
+        calipers::antipodal_pairs(&self.vertices)
+    }
+    pub fn max_distance(&self, other: &Polygon) -> (f32, Point, Point) {
+        calipers::max_distance(&self.vertices, &other.vertices)
+    }
+    pub fn min_distance(&self, other: &Polygon) -> (f32, Point, Point) {
+        calipers::min_distance(&self.vertices, &other.vertices)
+    }
+    // the sums and differences treat both polygons as their convex hulls
+    pub fn minkowski_sum(&self, other: &Polygon) -> Polygon {
+        minkowski::minkowski_sum(&self.vertices, &other.vertices)
+    }
+    pub fn minkowski_difference(&self, other: &Polygon) -> Polygon {
+        minkowski::minkowski_difference(&self.vertices, &other.vertices)
+    }
+    pub fn minkowski_sum_circle(&self, circle: &Circle) -> Polygon {
+        minkowski::minkowski_sum_circle(&self.vertices, circle)
+    }
+    pub fn penetration(&self, other: &Polygon) -> Option<Vector> {
+        minkowski::penetration(&self.vertices, &other.vertices)
+    }
+    // convex pieces using only the existing vertices
+    pub fn convex_decomposition(&self) -> Vec<Polygon> {
+        hertel_mehlhorn(&self.vertices)
+    }
+    // usually fewer pieces, but the cuts may end in new vertices
+    pub fn convex_decomposition_bayazit(&self) -> Vec<Polygon> {
+        bayazit(&self.vertices)
+    }
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        triangulation::triangulate(&self.vertices)
+    }
+    pub fn triangles(&self) -> Vec<Triangle> {
+        triangulation::to_triangles(&self.vertices, &self.triangulate())
     }
     pub fn closest_point(&self, point: Point) -> Point {
-        let sides = self.to_lines();
-        let mut best = sides.first().unwrap().closest_point(point);
-        for side in sides.iter().skip(1) {
+        let mut sides = self.edges();
+        let mut best = sides.next().unwrap().closest_point(point);
+        for side in sides {
             let candidate = side.closest_point(point);
             if candidate.distance_to(point) < best.distance_to(point) {
                 best = candidate;
@@ -39,19 +187,29 @@ impl Polygon {
         }
         best
     }
-    pub fn is_inside(&self, point: Point) -> bool {
-        let mut angle_sum = Angle::zero();
-
-        let n = self.vertices.len();
-        for i in 0..n {
-            let angle = Angle::from_vectors(
-                Vector::from_points(point, self.vertices[i]),
-                Vector::from_points(point, self.vertices[(i + 1) % n]),
-            );
-            angle_sum = angle_sum + angle;
+    pub fn locate(&self, point: Point) -> PointLocation {
+        let mut inside = false;
+        for side in self.edges() {
+            if side.closest_point(point) == point {
+                return PointLocation::Boundary;
+            }
+            let (a, b) = (side.origin, side.end);
+            // even-odd rule, count crossings of a ray towards +x
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
         }
-        float_eq::FloatEq::eq_abs(&angle_sum.deg, &360f64, &10e-3)
-            || float_eq::FloatEq::eq_abs(&angle_sum.deg, &0f64, &10e-3)
+    }
+    pub fn is_inside(&self, point: Point) -> bool {
+        self.locate(point) == PointLocation::Inside
     }
     pub fn to_bounding_box(&self) -> BoundingBox {
         let mut x_min = f32::INFINITY;
@@ -81,13 +239,131 @@ impl Polygon {
                 indices.push(idx);
             }
         }
-        if indices.len() > 0 {
+        if !indices.is_empty() {
             return Some(indices);
         }
         None
     }
+    // a polygon faces along its first edge, zero until it has one
+    fn heading(&self) -> Angle {
+        let first = match self.vertices.first() {
+            Some(&first) => first,
+            None => return Angle::zero(),
+        };
+        match self.vertices.iter().find(|&&vertex| vertex != first) {
+            Some(&next) => Vector::from_points(first, next).orientation(),
+            None => Angle::zero(),
+        }
+    }
 }
 
+impl Shape for Polygon {
+    fn get_type(&self) -> ShapeType {
+        ShapeType::Polygon
+    }
+    fn center(&self) -> Point {
+        self.to_bounding_box().center
+    }
+    fn enclosing_radius(&self) -> f32 {
+        let center = self.center();
+        self.vertices
+            .iter()
+            .map(|vertex| vertex.distance_to(center))
+            .fold(0.0, f32::max)
+    }
+    fn translate(&mut self, vector: Vector) {
+        for vertex in self.vertices.iter_mut() {
+            *vertex = *vertex + vector;
+        }
+    }
+    fn move_to(&mut self, point: Point) {
+        let vector = Vector::from_points(self.center(), point);
+        self.translate(vector);
+    }
+    fn rotate(&mut self, theta: Angle) {
+        let center = self.center();
+        self.rotate_about(center, theta);
+    }
+    fn rotate_to(&mut self, phi: Angle) {
+        let theta = phi - self.heading();
+        self.rotate(theta);
+    }
+    // The heading follows the first edge, not the outline as a whole. Anything that
+    // starts the ring elsewhere or drops its first edge, like reordering, `simplify` or
+    // `make_valid`, can turn the pose without turning the polygon.
+    fn pose(&self) -> Pose {
+        Pose::new(self.center(), self.heading())
+    }
+    // turning moves the centre of the bounding box, so turn before moving
+    fn set_pose(&mut self, pose: &Pose) {
+        self.rotate_to(pose.phi);
+        self.move_to(pose.position);
+    }
+    fn rotate_about(&mut self, point: Point, theta: Angle) {
+        for vertex in self.vertices.iter_mut() {
+            vertex.rotate_about(point, theta);
+        }
+    }
+    fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+    fn bounding_box(&self) -> BoundingBox {
+        self.to_bounding_box()
+    }
+    fn closest_point(&self, point: Point) -> Point {
+        Polygon::closest_point(self, point)
+    }
+    fn area(&self) -> f32 {
+        Polygon::area(self)
+    }
+    fn perimeter(&self) -> f32 {
+        Polygon::perimeter(self)
+    }
+    fn centroid(&self) -> Point {
+        Polygon::centroid(self)
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        Polygon::mass_properties(self, density)
+    }
+    fn enclosing_circle(&self) -> Circle {
+        minimum_enclosing_circle(&self.vertices)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Edges<'a> {
+    vertices: &'a [Point],
+    index: usize,
+}
+
+impl<'a> Edges<'a> {
+    pub fn new(vertices: &'a [Point]) -> Self {
+        Self { vertices, index: 0 }
+    }
+}
+
+impl Iterator for Edges<'_> {
+    type Item = Line;
+    fn next(&mut self) -> Option<Line> {
+        let n = self.vertices.len();
+        if self.index >= n {
+            return None;
+        }
+        let line = Line::new(
+            self.vertices[self.index],
+            self.vertices[(self.index + 1) % n],
+        );
+        self.index += 1;
+        Some(line)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.vertices.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Edges<'_> {}
+
 impl std::fmt::Display for Polygon {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let first = self.vertices.first();
@@ -103,8 +379,11 @@ impl std::fmt::Display for Polygon {
 
 #[cfg(test)]
 mod tests {
-    use crate::base::{Line, Point};
-    use crate::shape::Polygon;
+    use crate::algorithm::offset::JoinStyle;
+    use crate::algorithm::simplify::Target;
+    use crate::algorithm::validation::Orientation;
+    use crate::base::{Angle, Line, Point, Pose, Vector};
+    use crate::shape::{PointLocation, Polygon, Shape, Tessellation};
 
     #[test]
     fn test_to_lines() {
@@ -145,6 +424,24 @@ mod tests {
         assert_eq!(result, expected);
     }
     #[test]
+    fn test_contact_point() {
+        // the ray crosses both prongs, the first crossing is the contact
+        let u_shape = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(6.0, 4.0),
+            Point::new(4.0, 4.0),
+            Point::new(4.0, 1.0),
+            Point::new(2.0, 1.0),
+            Point::new(2.0, 4.0),
+            Point::new(0.0, 4.0),
+        ]);
+        let contact = u_shape.contact_point(Point::new(-2.0, 3.0), Vector::new(1.0, 0.0));
+        assert_eq!(contact, Some(Point::new(0.0, 3.0)));
+        let contact = u_shape.contact_point(Point::new(3.0, 3.0), Vector::new(1.0, 0.0));
+        assert_eq!(contact, Some(Point::new(4.0, 3.0)));
+    }
+    #[test]
     fn test_is_inside() {
         let point_a = Point::new(1.0, 0.0);
         let point_b = Point::new(1.0, 3.0);
@@ -153,12 +450,305 @@ mod tests {
         let poly = Polygon::new(&points);
         // test inside
         let point = Point::new(0.0, 2.0);
-        assert!(poly.is_inside(point) == true, "Point is inside");
+        assert!(poly.is_inside(point), "Point is inside");
         // test on border - not inside!
         let point = Point::new(1.0, 2.0);
-        assert!(poly.is_inside(point) == false, "Point is on border");
+        assert!(!poly.is_inside(point), "Point is on border");
         // test outside
         let point = Point::new(2.0, 2.0);
-        assert!(poly.is_inside(point) == false, "Point is outside");
+        assert!(!poly.is_inside(point), "Point is outside");
+    }
+    #[test]
+    fn test_locate() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(2.0, 1.0),
+            Point::new(0.0, 4.0),
+        ];
+        let poly = Polygon::new(&points);
+        assert_eq!(poly.locate(Point::new(1.0, 1.0)), PointLocation::Inside);
+        // in the notch of the concave polygon
+        assert_eq!(poly.locate(Point::new(2.0, 3.0)), PointLocation::Outside);
+        assert_eq!(poly.locate(Point::new(2.0, 0.0)), PointLocation::Boundary);
+        assert_eq!(poly.locate(Point::new(4.0, 4.0)), PointLocation::Boundary);
+        assert_eq!(poly.locate(Point::new(5.0, 1.0)), PointLocation::Outside);
+    }
+    #[test]
+    fn test_area() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 3.0),
+            Point::new(4.0, 3.0),
+            Point::new(4.0, 0.0),
+        ];
+        let poly = Polygon::new(&points);
+        assert_eq!(poly.area(), 12.0);
+    }
+    #[test]
+    fn test_signed_area() {
+        let ccw = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 3.0),
+            Point::new(0.0, 3.0),
+        ];
+        let mut cw = ccw.clone();
+        cw.reverse();
+        assert_eq!(Polygon::new(&ccw).signed_area(), 12.0);
+        assert_eq!(Polygon::new(&cw).signed_area(), -12.0);
+    }
+    #[test]
+    fn test_perimeter() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(3.0, 4.0),
+        ];
+        let poly = Polygon::new(&points);
+        assert_eq!(poly.perimeter(), 12.0);
+    }
+    #[test]
+    fn test_centroid() {
+        // L-shape made of a 2x1 and a 1x1 square
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let poly = Polygon::new(&points);
+        let expected = Point::new(5.0 / 6.0, 5.0 / 6.0);
+        assert_eq!(poly.centroid(), expected);
+        // centroid does not depend on the winding
+        let mut reversed = poly.clone();
+        reversed.vertices.reverse();
+        assert_eq!(reversed.centroid(), expected);
+    }
+    #[test]
+    fn test_enclosing_circle() {
+        // for a triangle the bounding box center is not the best center
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 8.66),
+        ];
+        let poly = Polygon::new(&points);
+        let circle = poly.enclosing_circle();
+        assert!(circle.radius() < poly.enclosing_radius());
+        for &vertex in points.iter() {
+            assert!(vertex.distance_to(circle.center()) <= circle.radius() + 1e-4);
+        }
+    }
+    #[test]
+    fn test_convex_hull() {
+        // clockwise concave polygon
+        let points = vec![
+            Point::new(0.0, 4.0),
+            Point::new(2.0, 1.0),
+            Point::new(4.0, 4.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 0.0),
+        ];
+        let hull = Polygon::new(&points).convex_hull();
+        assert_eq!(hull.vertices.len(), 4);
+        assert_eq!(hull.signed_area(), 16.0);
+    }
+    #[test]
+    fn test_offset() {
+        // clockwise input grows all the same
+        let polygon = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+        ]);
+        let grown = polygon.offset(1.0, JoinStyle::Miter(2.0)).unwrap();
+        assert_eq!(grown.area(), 16.0);
+        assert!(grown.polygons[0].outer.signed_area() > 0.0);
+        let shrunk = polygon.offset(-0.5, JoinStyle::Round).unwrap();
+        assert_eq!(shrunk.area(), 1.0);
+        // finer round joins hug the exact area closer
+        let exact = 4.0 + 8.0 + std::f32::consts::PI;
+        let coarse = polygon.offset(1.0, JoinStyle::Round).unwrap().area();
+        let fine = polygon
+            .offset_with(
+                1.0,
+                JoinStyle::Round,
+                Tessellation::new(0.001, 4, 1024, Angle::zero()),
+            )
+            .unwrap()
+            .area();
+        assert!((exact - fine).abs() < (exact - coarse).abs());
+        assert!((exact - fine).abs() < 0.01);
+    }
+    #[test]
+    fn test_orientation() {
+        let clockwise = [
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+        ];
+        assert_eq!(
+            Polygon::new(&clockwise).orientation(),
+            Orientation::Clockwise
+        );
+        let polygon = Polygon::new_counter_clockwise(&clockwise);
+        assert_eq!(polygon.orientation(), Orientation::CounterClockwise);
+        assert!(polygon.validate().is_convex);
+        // the normal points out of the polygon for either winding
+        let point = Point::new(1.0, 0.0);
+        let expected = Vector::new(0.0, -1.0);
+        assert_eq!(polygon.get_normal_vector_at(point), Some(expected));
+        assert_eq!(
+            Polygon::new(&clockwise).get_normal_vector_at(point),
+            Some(expected)
+        );
+    }
+    #[test]
+    fn test_pose() {
+        let triangle = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 2.0),
+        ]);
+        assert_eq!(
+            triangle.pose(),
+            Pose::new(Point::new(2.0, 1.0), Angle::zero())
+        );
+        let pose = Pose::new(Point::new(5.0, 5.0), Angle::new(90.0));
+        let placed = triangle.at_pose(&pose);
+        assert_eq!(placed.pose(), pose);
+        assert_eq!(placed.vertices[0], Point::new(6.0, 3.0));
+        assert_eq!(placed.vertices[1], Point::new(6.0, 7.0));
+        assert_eq!(placed.area(), triangle.area());
+        // turning to the same heading again changes nothing
+        let mut again = placed.clone();
+        again.rotate_to(Angle::new(90.0));
+        assert_eq!(again, placed);
+    }
+    #[test]
+    fn test_pose_simplify() {
+        // the dropped vertex sits on the far edge, the first edge survives
+        let polygon = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 2.0),
+            Point::new(3.0, 4.0),
+            Point::new(1.0, 3.0),
+            Point::new(-1.0, 2.0),
+        ]);
+        let simplified = polygon.simplify(Target::Tolerance(0.1), true);
+        assert_eq!(simplified.vertices.len(), 4);
+        assert_eq!(simplified.pose(), polygon.pose());
+        let reduced = polygon.simplify_by_area(Target::VertexCount(4), true);
+        assert_eq!(reduced.pose(), polygon.pose());
+    }
+    #[test]
+    fn test_convex_decomposition() {
+        let polygon = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ]);
+        for pieces in [
+            polygon.convex_decomposition(),
+            polygon.convex_decomposition_bayazit(),
+        ] {
+            assert_eq!(pieces.len(), 2);
+            assert!(pieces.iter().all(|piece| piece.validate().is_convex));
+            assert_eq!(pieces.iter().map(|piece| piece.area()).sum::<f32>(), 3.0);
+        }
+    }
+    #[test]
+    fn test_calipers() {
+        let polygon = Polygon::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.0),
+            Point::new(0.0, 1.0),
+        ]);
+        assert_eq!(polygon.diameter().0, 17f32.sqrt());
+        assert_eq!(polygon.width(), (1.0, Vector::new(0.0, 1.0)));
+        let mut other = polygon.clone();
+        other.translate(Vector::new(0.0, 3.0));
+        // parallel sides facing each other, the gap is measured straight across
+        assert_eq!(polygon.min_distance(&other).0, 2.0);
+        assert_eq!(polygon.max_distance(&other).0, 32f32.sqrt());
+        other.translate(Vector::new(2.0, -2.5));
+        assert_eq!(polygon.min_distance(&other).0, 0.0);
+    }
+    #[test]
+    fn test_minkowski() {
+        let robot = Polygon::new(&[
+            Point::new(-0.5, -0.5),
+            Point::new(0.5, -0.5),
+            Point::new(0.5, 0.5),
+            Point::new(-0.5, 0.5),
+        ]);
+        let obstacle = Polygon::new(&[
+            Point::new(2.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(3.0, 2.0),
+        ]);
+        // configuration space obstacle for the robot centre
+        let blocked = obstacle.minkowski_difference(&robot);
+        assert!(blocked.is_inside(Point::new(1.6, 0.0)));
+        assert!(!blocked.is_inside(Point::new(1.4, 0.0)));
+        assert_eq!(blocked, obstacle.minkowski_sum(&robot));
+        let mut moved = robot.clone();
+        moved.translate(Vector::new(1.75, 0.25));
+        let delta = moved.penetration(&obstacle).unwrap();
+        moved.translate(delta);
+        assert!(moved.penetration(&obstacle).is_none());
+    }
+    #[test]
+    fn test_simplify() {
+        // a square traced with a vertex every 0.5 units and a little jitter
+        let mut vertices = Vec::new();
+        for &(start, step) in [
+            (Point::new(0.0, 0.0), Vector::new(0.5, 0.0)),
+            (Point::new(4.0, 0.0), Vector::new(0.0, 0.5)),
+            (Point::new(4.0, 4.0), Vector::new(-0.5, 0.0)),
+            (Point::new(0.0, 4.0), Vector::new(0.0, -0.5)),
+        ]
+        .iter()
+        {
+            for i in 0..8 {
+                let jitter = step.get_normal_vector() * (0.02 * (i % 2) as f32);
+                vertices.push(start + step * i as f32 + jitter);
+            }
+        }
+        let polygon = Polygon::new(&vertices);
+        for simplified in [
+            polygon.simplify(Target::Tolerance(0.1), true),
+            polygon.simplify_by_area(Target::VertexCount(4), true),
+        ] {
+            assert_eq!(simplified.vertices.len(), 4);
+            assert!((simplified.area() - 16.0).abs() < 1e-4);
+        }
+    }
+    #[test]
+    fn test_triangles() {
+        let polygon = Polygon::new(&[
+            Point::new(0.0, 4.0),
+            Point::new(2.0, 1.0),
+            Point::new(4.0, 4.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 0.0),
+        ]);
+        assert_eq!(polygon.triangulate().len(), 3);
+        let triangles = polygon.triangles();
+        assert!(triangles
+            .iter()
+            .all(|triangle| triangle.signed_area() > 0.0));
+        let area: f32 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert_eq!(area, polygon.area());
     }
 }
@@ -0,0 +1,290 @@
+use crate::algorithm::offset::{offset_rings, JoinStyle};
+use crate::algorithm::triangulation;
+use crate::base::{Angle, Line, Point, Triangle, Vector};
+use crate::collision::BoundingBox;
+use crate::shape::{shape::*, MassProperties, MultiPolygon, PointLocation, Polygon, Tessellation};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolygonWithHoles {
+    pub outer: Polygon,
+    pub holes: Vec<Polygon>,
+}
+
+#[allow(dead_code)]
+impl PolygonWithHoles {
+    pub fn new(outer: Polygon, holes: Vec<Polygon>) -> Self {
+        Self { outer, holes }
+    }
+    pub fn rings(&self) -> impl Iterator<Item = &Polygon> {
+        std::iter::once(&self.outer).chain(self.holes.iter())
+    }
+    pub fn edges(&self) -> impl Iterator<Item = Line> + '_ {
+        self.rings().flat_map(|ring| ring.edges())
+    }
+    // outer vertices followed by the vertices of each hole, as indexed by `triangulate`
+    pub fn vertices(&self) -> Vec<Point> {
+        self.rings()
+            .flat_map(|ring| ring.vertices.iter().copied())
+            .collect()
+    }
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let holes: Vec<&[Point]> = self.holes.iter().map(|hole| &hole.vertices[..]).collect();
+        triangulation::triangulate_with_holes(&self.outer.vertices, &holes)
+    }
+    pub fn triangles(&self) -> Vec<Triangle> {
+        triangulation::to_triangles(&self.vertices(), &self.triangulate())
+    }
+    pub fn offset(&self, distance: f32, join: JoinStyle) -> Option<MultiPolygon> {
+        self.offset_with(distance, join, Tessellation::default())
+    }
+    pub fn offset_with(
+        &self,
+        distance: f32,
+        join: JoinStyle,
+        tessellation: Tessellation,
+    ) -> Option<MultiPolygon> {
+        let rings = self.oriented_rings();
+        let rings: Vec<&[Point]> = rings.iter().map(|ring| &ring[..]).collect();
+        offset_rings(&rings, distance, join, tessellation)
+    }
+    // copies of the rings wound counter-clockwise for the outer and clockwise for the holes
+    pub(crate) fn oriented_rings(&self) -> Vec<Vec<Point>> {
+        self.rings()
+            .enumerate()
+            .map(|(idx, ring)| {
+                let mut vertices = ring.vertices.clone();
+                if (ring.signed_area() > 0.0) != (idx == 0) {
+                    vertices.reverse();
+                }
+                vertices
+            })
+            .collect()
+    }
+    pub fn locate(&self, point: Point) -> PointLocation {
+        let location = self.outer.locate(point);
+        if location != PointLocation::Inside {
+            return location;
+        }
+        for hole in self.holes.iter() {
+            match hole.locate(point) {
+                PointLocation::Inside => return PointLocation::Outside,
+                PointLocation::Boundary => return PointLocation::Boundary,
+                PointLocation::Outside => (),
+            }
+        }
+        PointLocation::Inside
+    }
+    pub fn is_inside(&self, point: Point) -> bool {
+        self.locate(point) == PointLocation::Inside
+    }
+    pub fn area(&self) -> f32 {
+        let holes: f32 = self.holes.iter().map(|hole| hole.area()).sum();
+        self.outer.area() - holes
+    }
+    pub fn perimeter(&self) -> f32 {
+        self.rings().map(|ring| ring.perimeter()).sum()
+    }
+    pub fn centroid(&self) -> Point {
+        let outer_area = self.outer.area();
+        let mut area = outer_area;
+        let mut moment = self.outer.centroid().to_vector() * outer_area;
+        for hole in self.holes.iter() {
+            let hole_area = hole.area();
+            area -= hole_area;
+            moment = moment - hole.centroid().to_vector() * hole_area;
+        }
+        if area == 0.0 {
+            return self.outer.centroid();
+        }
+        (moment / area).to_point()
+    }
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        self.holes
+            .iter()
+            .fold(self.outer.mass_properties(density), |acc, hole| {
+                acc - hole.mass_properties(density)
+            })
+    }
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.outer.to_bounding_box()
+    }
+    pub fn closest_point(&self, point: Point) -> Point {
+        let mut best = self.outer.closest_point(point);
+        for hole in self.holes.iter() {
+            let candidate = hole.closest_point(point);
+            if candidate.distance_to(point) < best.distance_to(point) {
+                best = candidate;
+            }
+        }
+        best
+    }
+    pub fn center(&self) -> Point {
+        self.bounding_box().center
+    }
+    pub fn translate(&mut self, vector: Vector) {
+        self.outer.translate(vector);
+        for hole in self.holes.iter_mut() {
+            hole.translate(vector);
+        }
+    }
+    pub fn move_to(&mut self, point: Point) {
+        let vector = Vector::from_points(self.center(), point);
+        self.translate(vector);
+    }
+    pub fn rotate(&mut self, theta: Angle) {
+        let center = self.center();
+        self.rotate_about(center, theta);
+    }
+    pub fn rotate_about(&mut self, point: Point, theta: Angle) {
+        self.outer.rotate_about(point, theta);
+        for hole in self.holes.iter_mut() {
+            hole.rotate_about(point, theta);
+        }
+    }
+}
+
+impl From<Polygon> for PolygonWithHoles {
+    fn from(polygon: Polygon) -> Self {
+        Self::new(polygon, Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::offset::JoinStyle;
+    use crate::base::{Angle, Point, Vector};
+    use crate::collision::BoundingBox;
+    use crate::shape::{PointLocation, Polygon, PolygonWithHoles};
+
+    #[test]
+    fn test_locate() {
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners(),
+            )],
+        );
+        assert_eq!(poly.locate(Point::new(2.0, 2.0)), PointLocation::Inside);
+        assert_eq!(poly.locate(Point::new(5.0, 5.0)), PointLocation::Outside);
+        assert_eq!(poly.locate(Point::new(4.0, 5.0)), PointLocation::Boundary);
+        assert_eq!(poly.locate(Point::new(10.0, 5.0)), PointLocation::Boundary);
+        assert_eq!(poly.locate(Point::new(12.0, 5.0)), PointLocation::Outside);
+    }
+    #[test]
+    fn test_area() {
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![
+                Polygon::new(&BoundingBox::from_edges(1.0, 1.0, 3.0, 3.0).corners()),
+                Polygon::new(&BoundingBox::from_edges(5.0, 5.0, 8.0, 8.0).corners()),
+            ],
+        );
+        assert_eq!(poly.area(), 100.0 - 4.0 - 9.0);
+    }
+    #[test]
+    fn test_closest_point() {
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners(),
+            )],
+        );
+        // inside the hole the closest boundary is the hole itself
+        let result = poly.closest_point(Point::new(5.0, 4.5));
+        assert_eq!(result, Point::new(5.0, 4.0));
+        let result = poly.closest_point(Point::new(5.0, -3.0));
+        assert_eq!(result, Point::new(5.0, 0.0));
+    }
+    #[test]
+    fn test_edges() {
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners(),
+            )],
+        );
+        assert_eq!(poly.edges().count(), 8);
+    }
+    #[test]
+    fn test_move_and_rotate() {
+        let mut poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(1.0, 1.0, 3.0, 3.0).corners(),
+            )],
+        );
+        poly.move_to(Point::zero());
+        assert_eq!(poly.outer.vertices[0], Point::new(-5.0, -5.0));
+        assert_eq!(poly.holes[0].vertices[0], Point::new(-4.0, -4.0));
+        poly.rotate(Angle::new(90f64));
+        assert_eq!(poly.outer.vertices[0], Point::new(5.0, -5.0));
+        assert_eq!(poly.holes[0].vertices[0], Point::new(4.0, -4.0));
+        poly.translate(Vector::new(1.0, 1.0));
+        assert_eq!(poly.holes[0].vertices[0], Point::new(5.0, -3.0));
+    }
+    #[test]
+    fn test_triangles() {
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners(),
+            )],
+        );
+        let triangles = poly.triangles();
+        let area: f32 = triangles.iter().map(|triangle| triangle.area()).sum();
+        assert_eq!(area, poly.area());
+        assert!(triangles
+            .iter()
+            .all(|triangle| !triangle.contains(Point::new(5.0, 5.0))));
+    }
+    #[test]
+    fn test_offset() {
+        // the hole is given counter-clockwise and still shrinks as the polygon grows
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(3.0, 3.0, 7.0, 7.0).corners(),
+            )],
+        );
+        let grown = poly.offset(1.0, JoinStyle::Miter(2.0)).unwrap();
+        assert_eq!(grown.polygons[0].holes.len(), 1);
+        assert!((grown.area() - (144.0 - 4.0)).abs() < 1e-3);
+    }
+    #[test]
+    fn test_perimeter() {
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 10.0, 10.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(4.0, 4.0, 6.0, 6.0).corners(),
+            )],
+        );
+        assert_eq!(poly.perimeter(), 48.0);
+    }
+    #[test]
+    fn test_centroid() {
+        // hole on the right shifts the centroid to the left
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(0.0, 0.0, 4.0, 2.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(2.0, 0.5, 3.0, 1.5).corners(),
+            )],
+        );
+        let expected = Point::new((8.0 * 2.0 - 2.5) / 7.0, 1.0);
+        assert_eq!(poly.centroid(), expected);
+    }
+    #[test]
+    fn test_mass_properties() {
+        // a ring has the polar moment of the outer square minus the inner one
+        let poly = PolygonWithHoles::new(
+            Polygon::new(&BoundingBox::from_edges(-2.0, -2.0, 2.0, 2.0).corners()),
+            vec![Polygon::new(
+                &BoundingBox::from_edges(-1.0, -1.0, 1.0, 1.0).corners(),
+            )],
+        );
+        let props = poly.mass_properties(1.0);
+        assert!((props.mass - 12.0).abs() < 1e-4);
+        assert_eq!(props.centroid, Point::zero());
+        let expected = 16.0 * 32.0 / 12.0 - 4.0 * 8.0 / 12.0;
+        assert!((props.inertia() - expected).abs() < 1e-3);
+    }
+}
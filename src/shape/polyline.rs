@@ -0,0 +1,139 @@
+use crate::algorithm::offset::{offset_polyline, JoinStyle};
+use crate::algorithm::simplify::{douglas_peucker, visvalingam_whyatt, Target};
+use crate::base::{Angle, Line, Point, Vector};
+use crate::shape::{MultiPolygon, Tessellation};
+
+// An open chain of points, the first and last vertex are not connected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polyline {
+    pub vertices: Vec<Point>,
+}
+
+#[allow(dead_code)]
+impl Polyline {
+    pub fn new(vertices: &[Point]) -> Self {
+        Self {
+            vertices: vertices.to_vec(),
+        }
+    }
+    pub fn edges(&self) -> impl Iterator<Item = Line> + '_ {
+        self.vertices
+            .windows(2)
+            .map(|pair| Line::new(pair[0], pair[1]))
+    }
+    pub fn length(&self) -> f32 {
+        self.edges()
+            .map(|edge| edge.origin.distance_to(edge.end))
+            .sum()
+    }
+    // none for an empty polyline
+    pub fn closest_point(&self, point: Point) -> Option<Point> {
+        let mut best = *self.vertices.first()?;
+        for edge in self.edges() {
+            let candidate = edge.closest_point(point);
+            if candidate.distance_to(point) < best.distance_to(point) {
+                best = candidate;
+            }
+        }
+        Some(best)
+    }
+    pub fn translate(&mut self, vector: Vector) {
+        for vertex in self.vertices.iter_mut() {
+            *vertex = *vertex + vector;
+        }
+    }
+    pub fn rotate_about(&mut self, point: Point, theta: Angle) {
+        for vertex in self.vertices.iter_mut() {
+            vertex.rotate_about(point, theta);
+        }
+    }
+    pub fn offset(&self, distance: f32, join: JoinStyle) -> Option<MultiPolygon> {
+        self.offset_with(distance, join, Tessellation::default())
+    }
+    pub fn offset_with(
+        &self,
+        distance: f32,
+        join: JoinStyle,
+        tessellation: Tessellation,
+    ) -> Option<MultiPolygon> {
+        offset_polyline(&self.vertices, distance, join, tessellation)
+    }
+    pub fn simplify(&self, target: Target, preserve_topology: bool) -> Polyline {
+        Polyline {
+            vertices: douglas_peucker(&self.vertices, false, target, preserve_topology),
+        }
+    }
+    pub fn simplify_by_area(&self, target: Target, preserve_topology: bool) -> Polyline {
+        Polyline {
+            vertices: visvalingam_whyatt(&self.vertices, false, target, preserve_topology),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithm::offset::JoinStyle;
+    use crate::algorithm::simplify::Target;
+    use crate::base::Point;
+    use crate::shape::Polyline;
+
+    fn path() -> Polyline {
+        Polyline::new(&[
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(3.0, 4.0),
+        ])
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(path().length(), 7.0);
+        assert_eq!(path().edges().count(), 2);
+    }
+    #[test]
+    fn test_closest_point() {
+        assert_eq!(
+            path().closest_point(Point::new(5.0, 2.0)),
+            Some(Point::new(3.0, 2.0))
+        );
+        let single = Polyline::new(&[Point::new(1.0, 1.0)]);
+        assert_eq!(
+            single.closest_point(Point::zero()),
+            Some(Point::new(1.0, 1.0))
+        );
+        assert_eq!(Polyline::default().closest_point(Point::zero()), None);
+    }
+    #[test]
+    fn test_offset() {
+        // a straight stroke is a rectangle of the path length and twice the distance
+        let line = Polyline::new(&[Point::new(0.0, 0.0), Point::new(5.0, 0.0)]);
+        let stroke = line.offset(0.5, JoinStyle::Miter(2.0)).unwrap();
+        assert_eq!(stroke.len(), 1);
+        assert_eq!(stroke.polygons[0].outer.vertices.len(), 4);
+        assert!((stroke.area() - 5.0).abs() < 1e-4);
+        assert!(stroke.is_inside(Point::new(2.5, 0.4)));
+        assert!(!stroke.is_inside(Point::new(5.2, 0.0)));
+    }
+    #[test]
+    fn test_simplify() {
+        let mut vertices = vec![Point::new(0.0, 0.0)];
+        for i in 1..10 {
+            vertices.push(Point::new(i as f32, 0.01 * (i % 2) as f32));
+        }
+        vertices.push(Point::new(10.0, 5.0));
+        let line = Polyline::new(&vertices);
+        let expected = vec![
+            Point::new(0.0, 0.0),
+            Point::new(9.0, 0.01),
+            Point::new(10.0, 5.0),
+        ];
+        assert_eq!(
+            line.simplify(Target::Tolerance(0.1), true).vertices,
+            expected
+        );
+        assert_eq!(
+            line.simplify_by_area(Target::VertexCount(3), true).vertices,
+            expected
+        );
+    }
+}
@@ -1,15 +1,15 @@
-use crate::base::{Angle, Point, Scale, Size, Vector};
+use crate::base::{Angle, Point, Pose, Scale, Size, Vector};
 use crate::collision::BoundingBox;
-use crate::shape::{shape::*, Polygon};
-use std::cell::RefCell;
+use crate::shape::{shape::*, Circle, MassProperties, Polygon};
+use std::sync::OnceLock;
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default)]
 pub struct Rectangle {
     center: Point,
     size: Size,
     phi: Angle,
-    _polygon: RefCell<Option<Polygon>>,
-    _bounding_box: RefCell<Option<BoundingBox>>,
+    _polygon: OnceLock<Polygon>,
+    _bounding_box: OnceLock<BoundingBox>,
 }
 
 #[allow(dead_code)]
@@ -19,8 +19,8 @@ impl Rectangle {
             center,
             size,
             phi,
-            _polygon: RefCell::new(None),
-            _bounding_box: RefCell::new(None),
+            _polygon: OnceLock::new(),
+            _bounding_box: OnceLock::new(),
         }
     }
     pub fn size(&self) -> Size {
@@ -37,13 +37,15 @@ impl Rectangle {
         self.size = size;
         self.invalidate();
     }
-    fn invalidate(&self) {
-        *self._polygon.borrow_mut() = None;
-        *self._bounding_box.borrow_mut() = None;
+    pub(crate) fn cached_polygon(&self) -> &Polygon {
+        self._polygon.get_or_init(|| self.create_polygon())
     }
-    fn create_polygon(&self) {
-        let mut vertices = Vec::new();
-        vertices.reserve(4);
+    fn invalidate(&mut self) {
+        self._polygon = OnceLock::new();
+        self._bounding_box = OnceLock::new();
+    }
+    fn create_polygon(&self) -> Polygon {
+        let mut vertices = Vec::with_capacity(4);
         let half_size = self.size / 2.0;
         let w_cos = half_size.w * self.phi.cos() as f32;
         let w_sin = half_size.w * self.phi.sin() as f32;
@@ -53,10 +55,14 @@ impl Rectangle {
         vertices.push(self.center + Vector::new(w_cos + h_sin, w_sin - h_cos));
         vertices.push(self.center + Vector::new(w_cos - h_sin, w_sin + h_cos));
         vertices.push(self.center + Vector::new(-w_cos - h_sin, -w_sin + h_cos));
-        *self._polygon.borrow_mut() = Some(Polygon { vertices });
+        Polygon { vertices }
     }
-    fn create_bounding_box(&self) {
-        *self._bounding_box.borrow_mut() = Some(self.polygon().to_bounding_box());
+}
+
+// the caches are left out, they only hold what the other fields determine
+impl PartialEq for Rectangle {
+    fn eq(&self, other: &Self) -> bool {
+        self.center == other.center && self.size == other.size && self.phi == other.phi
     }
 }
 
@@ -86,26 +92,51 @@ impl Shape for Rectangle {
         self.phi = phi;
         self.invalidate();
     }
+    fn pose(&self) -> Pose {
+        Pose::new(self.center, self.phi)
+    }
     fn rotate_about(&mut self, point: Point, theta: Angle) {
         self.center.rotate_about(point, theta);
         self.phi = self.phi + theta;
         self.invalidate();
     }
-    fn polygon(&self) -> Polygon {
-        if *self._polygon.borrow() == None {
-            self.create_polygon();
-        }
-        (*self._polygon.borrow()).clone().unwrap()
+    fn vertices(&self) -> &[Point] {
+        &self.cached_polygon().vertices
     }
     fn bounding_box(&self) -> BoundingBox {
-        if *self._bounding_box.borrow() == None {
-            self.create_bounding_box();
-        }
-        (*self._bounding_box.borrow()).clone().unwrap()
+        *self
+            ._bounding_box
+            .get_or_init(|| self.cached_polygon().to_bounding_box())
     }
     fn closest_point(&self, point: Point) -> Point {
-        let polygon = self.polygon();
-        polygon.closest_point(point)
+        self.cached_polygon().closest_point(point)
+    }
+    fn area(&self) -> f32 {
+        self.size.w * self.size.h
+    }
+    fn perimeter(&self) -> f32 {
+        2.0 * (self.size.w + self.size.h)
+    }
+    fn centroid(&self) -> Point {
+        self.center
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        let mass = density * self.area();
+        // spread along the local axes, rotated into the world frame
+        let along = mass * self.size.w.powf(2.0) / 12.0;
+        let across = mass * self.size.h.powf(2.0) / 12.0;
+        let cos = self.phi.cos() as f32;
+        let sin = self.phi.sin() as f32;
+        MassProperties::new(
+            mass,
+            self.center,
+            along * sin * sin + across * cos * cos,
+            along * cos * cos + across * sin * sin,
+            (along - across) * sin * cos,
+        )
+    }
+    fn enclosing_circle(&self) -> Circle {
+        Circle::new(self.center, self.enclosing_radius())
     }
 }
 
@@ -147,6 +178,15 @@ mod tests {
         assert_eq!(rect, expected);
     }
     #[test]
+    fn test_oriented_bounding_box() {
+        let rect = Rectangle::new(Point::new(1.0, 2.0), Size::new(3.0, 1.0), Angle::new(30f64));
+        let fitted = rect.oriented_bounding_box();
+        assert!((fitted.area() - 3.0).abs() < 1e-4);
+        assert_eq!(fitted.center(), rect.center());
+        let aabb = rect.bounding_box();
+        assert!((aabb.x2() - aabb.x1()) * (aabb.y2() - aabb.y1()) > 4.0);
+    }
+    #[test]
     fn test_rotate() {
         let mut rect = Rectangle::new(Point::new(10.0, -5.0), Size::new(2.0, 1.0), Angle::zero());
         rect.rotate(Angle::new(45f64));
@@ -232,4 +272,29 @@ mod tests {
             vert_d
         );
     }
+    #[test]
+    fn test_area_perimeter_centroid() {
+        let rect = Rectangle::new(
+            Point::new(10.0, -5.0),
+            Size::new(4.0, 2.0),
+            Angle::new(30f64),
+        );
+        assert_eq!(rect.area(), 8.0);
+        assert_eq!(rect.perimeter(), 12.0);
+        assert_eq!(rect.centroid(), Point::new(10.0, -5.0));
+        // exact formulas agree with the polygon approximation
+        assert!((rect.area() - rect.polygon().area()).abs() < 1e-4);
+    }
+    #[test]
+    fn test_mass_properties() {
+        let rect = Rectangle::new(Point::new(3.0, 1.0), Size::new(4.0, 2.0), Angle::new(30f64));
+        let exact = rect.mass_properties(2.0);
+        let approx = rect.polygon().mass_properties(2.0);
+        assert!((exact.mass - approx.mass).abs() < 1e-3);
+        assert!((exact.ixx - approx.ixx).abs() < 1e-3);
+        assert!((exact.iyy - approx.iyy).abs() < 1e-3);
+        assert!((exact.ixy - approx.ixy).abs() < 1e-3);
+        let (angle, _, _) = exact.principal_axes();
+        assert_eq!(angle, Angle::new(30f64));
+    }
 }
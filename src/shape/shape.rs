@@ -1,12 +1,20 @@
-use crate::base::{Angle, Line, Point, Vector};
+use crate::algorithm::calipers::minimum_area_rectangle;
+use crate::algorithm::enclosing::minimum_enclosing_circle;
+use crate::algorithm::validation::{orientation, Orientation};
+use crate::base::{Angle, Line, Point, Pose, Vector};
 use crate::collision::BoundingBox;
-use crate::shape::Polygon;
+use crate::shape::{Circle, Edges, MassProperties, Polygon, Rectangle};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ShapeType {
+    Point,
+    BoundingBox,
+    Ray,
     Circle,
     Rectangle,
     Hexagon,
+    Polygon,
+    Compound,
 }
 
 pub trait Shape {
@@ -18,37 +26,129 @@ pub trait Shape {
     fn rotate(&mut self, theta: Angle);
     fn rotate_to(&mut self, phi: Angle);
     fn rotate_about(&mut self, point: Point, theta: Angle);
-    fn polygon(&self) -> Polygon;
+    // shapes without an orientation of their own always face zero
+    fn pose(&self) -> Pose {
+        Pose::new(self.center(), Angle::zero())
+    }
+    fn set_pose(&mut self, pose: &Pose) {
+        self.move_to(pose.position);
+        self.rotate_to(pose.phi);
+    }
+    fn at_pose(&self, pose: &Pose) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut shape = self.clone();
+        shape.set_pose(pose);
+        shape
+    }
+    // borrowed from the shape, unlike `polygon` nothing is copied
+    fn vertices(&self) -> &[Point];
+    fn edges(&self) -> Edges<'_> {
+        Edges::new(self.vertices())
+    }
+    fn polygon(&self) -> Polygon {
+        Polygon::new(self.vertices())
+    }
     fn bounding_box(&self) -> BoundingBox;
     fn closest_point(&self, point: Point) -> Point;
+    fn area(&self) -> f32 {
+        self.polygon().area()
+    }
+    fn perimeter(&self) -> f32 {
+        self.polygon().perimeter()
+    }
+    fn centroid(&self) -> Point {
+        self.polygon().centroid()
+    }
+    fn mass_properties(&self, density: f32) -> MassProperties {
+        self.polygon().mass_properties(density)
+    }
+    fn enclosing_circle(&self) -> Circle {
+        minimum_enclosing_circle(self.vertices())
+    }
+    // smallest rotated rectangle around the shape, tighter than the axis aligned bounding box
+    fn oriented_bounding_box(&self) -> Rectangle {
+        minimum_area_rectangle(self.vertices())
+    }
     fn contact_point(&self, origin: Point, direction: Vector) -> Option<Point> {
-        let extended = direction.get_unit_vector()
-            * (origin.distance_to(self.center()) + self.enclosing_radius());
-        let line = Line::from_vector(origin, extended);
-        let (ia, ib) = line.intersection_polygon(&self.polygon());
-        if let Some(intersection_a) = ia {
-            if let Some(intersection_b) = ib {
-                // Two intersections
-                if origin.distance_to(intersection_a) < origin.distance_to(intersection_b) {
-                    return Some(intersection_a);
-                } else {
-                    return Some(intersection_b);
-                }
-            } else {
-                return Some(intersection_a);
-            }
-        }
-        None
+        let reach = origin.distance_to(self.center()) + self.enclosing_radius();
+        contact_on_outline(self.vertices(), origin, direction, reach)
     }
     fn get_normal_vector_at(&self, point: Point) -> Option<Vector> {
-        let sides = self.polygon().to_lines();
-        for side in sides.iter() {
-            if side.is_on_line(point) {
-                return Some(side.to_vector().get_normal_vector().get_unit_vector());
+        normal_on_outline(self.vertices(), point)
+    }
+    // negative inside, zero on the outline
+    fn signed_distance(&self, point: Point) -> f32 {
+        signed_distance_to_outline(self.vertices(), point)
+    }
+    // Points the way the signed distance grows fastest, a unit vector except where it
+    // is flat. Estimated from central differences unless a shape knows better.
+    fn gradient(&self, point: Point) -> Vector {
+        let h = GRADIENT_STEP * self.enclosing_radius().max(1.0);
+        central_gradient(|at| self.signed_distance(at), point, h)
+    }
+}
+
+// relative to the size of the shape
+pub(crate) const GRADIENT_STEP: f32 = 1e-3;
+
+// central differences with step `h`, unit length except where the field is flat
+pub(crate) fn central_gradient(distance: impl Fn(Point) -> f32, point: Point, h: f32) -> Vector {
+    let (dx, dy) = (Vector::new(h, 0.0), Vector::new(0.0, h));
+    let gradient = Vector::new(
+        distance(point + dx) - distance(point - dx),
+        distance(point + dy) - distance(point - dy),
+    );
+    if gradient.magnitude() == 0.0 {
+        return gradient;
+    }
+    gradient.get_unit_vector()
+}
+
+// The defaults above, for shapes that wrap others and fall back to them for some kinds.
+pub(crate) fn contact_on_outline(
+    vertices: &[Point],
+    origin: Point,
+    direction: Vector,
+    reach: f32,
+) -> Option<Point> {
+    let line = Line::from_vector(origin, direction.get_unit_vector() * reach);
+    // the nearest crossing comes first
+    line.intersection_edges(Edges::new(vertices)).0
+}
+
+pub(crate) fn normal_on_outline(vertices: &[Point], point: Point) -> Option<Vector> {
+    // points outwards whichever way the vertices wind
+    let outwards = match orientation(vertices) {
+        Orientation::Clockwise => -1.0,
+        _ => 1.0,
+    };
+    for side in Edges::new(vertices) {
+        if side.origin != side.end && side.is_on_line(point) {
+            return Some(side.to_vector().get_normal_vector().get_unit_vector() * outwards);
+        }
+    }
+    None
+}
+
+pub(crate) fn signed_distance_to_outline(vertices: &[Point], point: Point) -> f32 {
+    let mut distance = f32::MAX;
+    let mut inside = false;
+    for side in Edges::new(vertices) {
+        distance = distance.min(side.closest_point(point).distance_to(point));
+        let (a, b) = (side.origin, side.end);
+        // even-odd rule, as in `Polygon::locate`
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < x {
+                inside = !inside;
             }
         }
-        None
     }
-    //fn to_enclosing_circle(&self) -> Circle;
-    //fn center_of_gravity(&self) -> Point
+    if inside {
+        -distance
+    } else {
+        distance
+    }
 }
@@ -0,0 +1,129 @@
+use std::f32::consts::PI;
+
+use crate::base::{Angle, Point, Vector};
+
+// How finely curves are turned into polygons. The segment count follows from the
+// largest allowed gap between a chord and the arc it replaces, clamped to the
+// segment bounds of a full turn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tessellation {
+    pub max_error: f32,
+    pub min_segments: usize,
+    pub max_segments: usize,
+    // direction of the first vertex of a full turn
+    pub start: Angle,
+}
+
+impl Default for Tessellation {
+    fn default() -> Self {
+        Self {
+            max_error: 0.25,
+            min_segments: 8,
+            max_segments: 512,
+            start: Angle::zero(),
+        }
+    }
+}
+
+impl Tessellation {
+    pub fn new(max_error: f32, min_segments: usize, max_segments: usize, start: Angle) -> Self {
+        assert!(max_error > 0.0, "Expected a positive chord error");
+        assert!(
+            min_segments <= max_segments,
+            "Expected min_segments <= max_segments"
+        );
+        Self {
+            max_error,
+            min_segments,
+            max_segments,
+            start,
+        }
+    }
+    // Segments for an arc of `sweep` radians. Full turns are rounded up to a multiple
+    // of four, so the extreme points along both axes are vertices.
+    pub fn segments(&self, radius: f32, sweep: f32) -> usize {
+        let sweep = sweep.abs().min(2.0 * PI);
+        let fraction = sweep / (2.0 * PI);
+        let step = if self.max_error >= radius {
+            PI
+        } else {
+            2.0 * (1.0 - self.max_error / radius).acos()
+        };
+        let low = (self.min_segments as f32 * fraction).ceil();
+        let high = (self.max_segments as f32 * fraction).ceil();
+        let count = (sweep / step).ceil().max(low).min(high).max(1.0) as usize;
+        if fraction == 1.0 {
+            (count + 3) / 4 * 4
+        } else {
+            count
+        }
+    }
+    // counter-clockwise, starting at `start` and without repeating the first vertex
+    pub fn circle(&self, center: Point, radius: f32) -> Vec<Point> {
+        let count = self.segments(radius, 2.0 * PI);
+        let step = 2.0 * PI / count as f32;
+        let start = self.start.to_rad32();
+        (0..count)
+            .map(|i| {
+                let phi = start + i as f32 * step;
+                center + Vector::new(radius * phi.cos(), radius * phi.sin())
+            })
+            .collect()
+    }
+    // both end points included, the sweep is in radians and runs clockwise when negative
+    pub fn arc(&self, center: Point, radius: f32, from: Angle, sweep: f32) -> Vec<Point> {
+        let count = self.segments(radius, sweep);
+        let start = from.to_rad32();
+        (0..=count)
+            .map(|i| {
+                let phi = start + sweep * i as f32 / count as f32;
+                center + Vector::new(radius * phi.cos(), radius * phi.sin())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point};
+    use crate::shape::Tessellation;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_segments() {
+        let tessellation = Tessellation::default();
+        // small circles are held up by the minimum, large ones by the error bound
+        assert_eq!(tessellation.segments(1.0, 2.0 * PI), 8);
+        assert_eq!(tessellation.segments(10.0, 2.0 * PI), 16);
+        let large = tessellation.segments(1000.0, 2.0 * PI);
+        assert!(large > 128 && large <= 512 && large % 4 == 0);
+        let coarse = Tessellation::new(1.0, 4, 64, Angle::zero());
+        assert_eq!(coarse.segments(1e6, 2.0 * PI), 64);
+        assert_eq!(coarse.segments(1e6, PI / 2.0), 16);
+        assert_eq!(coarse.segments(0.5, PI / 2.0), 1);
+    }
+    #[test]
+    fn test_error_bound() {
+        let tessellation = Tessellation::new(0.05, 3, 1000, Angle::zero());
+        for &radius in [0.5, 3.0, 40.0].iter() {
+            let vertices = tessellation.circle(Point::zero(), radius);
+            let n = vertices.len();
+            for i in 0..n {
+                let mid = (vertices[i] + vertices[(i + 1) % n]) / 2.0;
+                assert!(radius - mid.distance_to(Point::zero()) <= 0.05 + 1e-5);
+            }
+        }
+    }
+    #[test]
+    fn test_arc() {
+        let tessellation = Tessellation::default();
+        let arc = tessellation.arc(Point::zero(), 2.0, Angle::new(90.0), -PI);
+        assert_eq!(arc[0], Point::new(0.0, 2.0));
+        assert_eq!(*arc.last().unwrap(), Point::new(0.0, -2.0));
+        // clockwise, so the arc bulges to the right
+        assert!(arc.iter().all(|point| point.x >= -1e-5));
+        let start = Tessellation::new(0.25, 8, 512, Angle::new(45.0));
+        let first = start.circle(Point::zero(), 2.0)[0];
+        assert_eq!(first, Point::new(2f32.sqrt(), 2f32.sqrt()));
+    }
+}
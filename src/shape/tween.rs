@@ -0,0 +1,288 @@
+use std::f32::consts::PI;
+
+use crate::base::{Point, Pose, Size, Vector};
+use crate::collision::{BoundingBox, Ray};
+use crate::shape::{
+    AnyShape, Circle, CompoundChild, CompoundShape, Hexagon, Polygon, Primitive, Rectangle, Shape,
+    ShapeValue,
+};
+
+// Maps the linear progress of a transition onto the one shown. Input outside [0, 1]
+// is clamped, the output starts at 0 and ends at 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut if t < 0.5 => 2.0 * t * t,
+            Easing::QuadInOut => 1.0 - 2.0 * (1.0 - t) * (1.0 - t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::CubicInOut => 1.0 - 4.0 * (1.0 - t).powi(3),
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => (1.0 - (t * PI).cos()) / 2.0,
+        }
+    }
+}
+
+// Blends two shapes of the same kind, t = 0 gives self and t = 1 gives other.
+// Positions and sizes move in a straight line, angles turn the short way round.
+// None when the two cannot be matched up, like polygons with different vertex counts.
+pub trait Tween: Sized {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self>;
+    fn tween_eased(&self, other: &Self, t: f32, easing: Easing) -> Option<Self> {
+        self.tween(other, easing.apply(t))
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn lerp_point(a: Point, b: Point, t: f32) -> Point {
+    a + Vector::from_points(a, b) * t
+}
+
+fn lerp_size(a: Size, b: Size, t: f32) -> Size {
+    Size::new(lerp(a.w, b.w, t), lerp(a.h, b.h, t))
+}
+
+impl Tween for Pose {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(self.lerp(other, t))
+    }
+}
+
+impl Tween for BoundingBox {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(BoundingBox::new(
+            lerp_point(self.center, other.center, t),
+            lerp_size(self.half, other.half, t),
+        ))
+    }
+}
+
+impl Tween for Ray {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(Ray::new(
+            lerp_point(self.origin, other.origin, t),
+            self.direction.lerp(other.direction, t as f64),
+            lerp(self.length, other.length, t),
+        ))
+    }
+}
+
+// keeps the tessellation of self
+impl Tween for Circle {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(Circle::with_tessellation(
+            lerp_point(self.center(), other.center(), t),
+            lerp(self.radius(), other.radius(), t),
+            self.tessellation(),
+        ))
+    }
+}
+
+impl Tween for Rectangle {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        let pose = self.pose().lerp(&other.pose(), t);
+        let size = lerp_size(self.size(), other.size(), t);
+        Some(Rectangle::new(pose.position, size, pose.phi))
+    }
+}
+
+impl Tween for Hexagon {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        let pose = self.pose().lerp(&other.pose(), t);
+        let side = lerp(self.side(), other.side(), t);
+        Some(Hexagon::new(pose.position, side, pose.phi))
+    }
+}
+
+// vertex by vertex, so both should start at corresponding corners
+impl Tween for Polygon {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.vertices.len() != other.vertices.len() {
+            return None;
+        }
+        let vertices = self
+            .vertices
+            .iter()
+            .zip(other.vertices.iter())
+            .map(|(&a, &b)| lerp_point(a, b, t))
+            .collect();
+        Some(Polygon { vertices })
+    }
+}
+
+impl Tween for Primitive {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        match (self, other) {
+            (Primitive::Circle(a), Primitive::Circle(b)) => a.tween(b, t).map(Primitive::from),
+            (Primitive::Rectangle(a), Primitive::Rectangle(b)) => {
+                a.tween(b, t).map(Primitive::from)
+            }
+            (Primitive::Hexagon(a), Primitive::Hexagon(b)) => a.tween(b, t).map(Primitive::from),
+            (Primitive::Polygon(a), Primitive::Polygon(b)) => a.tween(b, t).map(Primitive::from),
+            _ => None,
+        }
+    }
+}
+
+// children are paired up by index and blended in the compound's own frame
+impl Tween for CompoundShape {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.children().len() != other.children().len() {
+            return None;
+        }
+        let children = self
+            .children()
+            .iter()
+            .zip(other.children().iter())
+            .map(|(a, b)| {
+                let shape = a.shape.tween(&b.shape, t)?;
+                let offset = a.offset + (b.offset - a.offset) * t;
+                let rotation = a.rotation.lerp(b.rotation, t as f64);
+                Some(CompoundChild::new(shape, offset, rotation))
+            })
+            .collect::<Option<Vec<CompoundChild>>>()?;
+        let pose = self.pose().lerp(&other.pose(), t);
+        Some(CompoundShape::new(pose.position, pose.phi, children))
+    }
+}
+
+impl Tween for AnyShape {
+    fn tween(&self, other: &Self, t: f32) -> Option<Self> {
+        match (self.value(), other.value()) {
+            (ShapeValue::Point(a), ShapeValue::Point(b)) => Some(lerp_point(*a, *b, t).into()),
+            (ShapeValue::BoundingBox(a), ShapeValue::BoundingBox(b)) => {
+                a.tween(b, t).map(AnyShape::from)
+            }
+            (ShapeValue::Ray(a), ShapeValue::Ray(b)) => a.tween(b, t).map(AnyShape::from),
+            (ShapeValue::Circle(a), ShapeValue::Circle(b)) => a.tween(b, t).map(AnyShape::from),
+            (ShapeValue::Rectangle(a), ShapeValue::Rectangle(b)) => {
+                a.tween(b, t).map(AnyShape::from)
+            }
+            (ShapeValue::Hexagon(a), ShapeValue::Hexagon(b)) => a.tween(b, t).map(AnyShape::from),
+            (ShapeValue::Polygon(a), ShapeValue::Polygon(b)) => a.tween(b, t).map(AnyShape::from),
+            (ShapeValue::Compound(a), ShapeValue::Compound(b)) => a.tween(b, t).map(AnyShape::from),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::base::{Angle, Point, Pose, Size, Vector};
+    use crate::shape::{
+        AnyShape, Circle, CompoundChild, CompoundShape, Easing, Polygon, Rectangle, Shape,
+        ShapeValue, Tween,
+    };
+
+    #[test]
+    fn test_easing() {
+        let all = [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::SineIn,
+            Easing::SineOut,
+            Easing::SineInOut,
+        ];
+        for easing in all.iter() {
+            assert!(easing.apply(0.0).abs() < 1e-6);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6);
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0));
+            assert_eq!(easing.apply(2.0), easing.apply(1.0));
+            // never runs backwards
+            let steps: Vec<f32> = (0..=20).map(|i| easing.apply(i as f32 / 20.0)).collect();
+            assert!(steps.windows(2).all(|pair| pair[1] >= pair[0]));
+        }
+        assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Easing::QuadOut.apply(0.5), 0.75);
+        assert!((Easing::CubicInOut.apply(0.5) - 0.5).abs() < 1e-6);
+    }
+    #[test]
+    fn test_tween() {
+        let a = Rectangle::new(Point::zero(), Size::new(2.0, 2.0), Angle::new(350.0));
+        let b = Rectangle::new(Point::new(10.0, 0.0), Size::new(6.0, 4.0), Angle::new(30.0));
+        let half = a.tween(&b, 0.5).unwrap();
+        assert_eq!(
+            half.pose(),
+            Pose::new(Point::new(5.0, 0.0), Angle::new(10.0))
+        );
+        assert_eq!(half.size(), Size::new(4.0, 3.0));
+        assert_eq!(a.tween(&b, 1.0).unwrap(), b);
+        let eased = a.tween_eased(&b, 0.5, Easing::QuadIn).unwrap();
+        assert_eq!(eased.center(), Point::new(2.5, 0.0));
+
+        let triangle = Polygon::new(&[Point::zero(), Point::new(2.0, 0.0), Point::new(0.0, 2.0)]);
+        let mut moved = triangle.clone();
+        moved.translate(Vector::new(0.0, 4.0));
+        assert_eq!(
+            triangle.tween(&moved, 0.25).unwrap().vertices[1],
+            Point::new(2.0, 1.0)
+        );
+        let square = Rectangle::new(Point::zero(), Size::new(1.0, 1.0), Angle::zero()).polygon();
+        assert_eq!(triangle.tween(&square, 0.5), None);
+    }
+    #[test]
+    fn test_tween_mixed() {
+        let circle: AnyShape = Circle::new(Point::zero(), 1.0).into();
+        let larger: AnyShape = Circle::new(Point::new(0.0, 2.0), 3.0).into();
+        let half = circle.tween(&larger, 0.5).unwrap();
+        if let ShapeValue::Circle(half) = half.value() {
+            assert_eq!(half.center(), Point::new(0.0, 1.0));
+            assert_eq!(half.radius(), 2.0);
+        } else {
+            panic!("Expected a circle");
+        }
+        let point: AnyShape = Point::zero().into();
+        assert_eq!(circle.tween(&point, 0.5), None);
+
+        let wheel = Circle::new(Point::zero(), 1.0);
+        let a = CompoundShape::new(
+            Point::zero(),
+            Angle::zero(),
+            vec![CompoundChild::new(
+                wheel.clone(),
+                Vector::new(2.0, 0.0),
+                Angle::zero(),
+            )],
+        );
+        let b = a.at_pose(&Pose::new(Point::new(0.0, 4.0), Angle::new(90.0)));
+        let half = a.tween(&b, 0.5).unwrap();
+        assert_eq!(
+            half.pose(),
+            Pose::new(Point::new(0.0, 2.0), Angle::new(45.0))
+        );
+        let placed = half.placed()[0].shape().center();
+        assert_eq!(placed, Point::new(2f32.sqrt(), 2.0 + 2f32.sqrt()));
+        let mut more = b.clone();
+        more.push(CompoundChild::new(wheel, Vector::zero(), Angle::zero()));
+        assert_eq!(a.tween(&more, 0.5), None);
+    }
+}